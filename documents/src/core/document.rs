@@ -1,17 +1,30 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
 use std::path::Path;
 
 use anyhow::Context;
 
+use crate::core::dtype::DocumentType;
+use crate::core::field::{Date, FieldValue};
+use crate::core::language;
 use crate::core::metadata::{DocumentMetadata, DocumentMetadataBuilder, DocumentSource};
+use crate::read::archive;
+use crate::read::code::{COMMENTS_FIELD, STRINGS_FIELD};
+use crate::read::encoding::read_text;
+use crate::read::{CodeReader, EpubReader, OfficeFormat, OfficeReader, ReadOptions, Record};
 use crate::read_to_string;
 use crate::DocumentId;
 
 /// Structure that represents a document. Document in this
 /// case is any part of ++++++
+///
+/// Besides its page content, a document may have named text fields,
+/// e.g. the comments of a source file, which are indexed separately.
 pub struct Document {
     doc_id: DocumentId,
     page_content: String,
     metadata: DocumentMetadata,
+    text_fields: BTreeMap<String, String>,
 }
 
 impl Document {
@@ -28,18 +41,130 @@ impl Document {
             doc_id,
             page_content,
             metadata,
+            text_fields: BTreeMap::new(),
         }
     }
 
+    /// Adds a named text field to the document. Blank fields are dropped.
+    pub fn with_text_field(mut self, name: &str, text: String) -> Self {
+        if !text.trim().is_empty() {
+            self.text_fields.insert(name.to_string(), text);
+        }
+        self
+    }
+
     /// Loads a document from file.
     pub fn from_file(doc_id: DocumentId, path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_file_with_options(doc_id, path, &ReadOptions::default())
+    }
+
+    /// Loads a document from file, reading its contents with the
+    /// specified options.
+    ///
+    /// Besides the metadata that the document declares, e.g. the front
+    /// matter of a Markdown document, the dates, the size and the MIME
+    /// type of its file are extracted, and the word count and language
+    /// of its content. The declared metadata take precedence.
+    pub fn from_file_with_options(
+        doc_id: DocumentId,
+        path: impl AsRef<Path>,
+        options: &ReadOptions,
+    ) -> anyhow::Result<Self> {
         let path = path.as_ref().to_path_buf();
-        let content =
-            read_to_string(&path).context(format!("Failed to read file {}", path.display()))?;
-        let metadata = DocumentMetadataBuilder::new()
-            .with_source(DocumentSource::File(path))
+        let context = || format!("Failed to read file {}", path.display());
+        let document_type = DocumentType::detect(&path)
+            .with_context(context)?
+            .context(format!("Unsupported file type: {}", path.display()))?;
+        let builder = DocumentMetadataBuilder::new().with_document_type(document_type);
+        let mut text_fields = Vec::new();
+        let (content, builder) = match document_type {
+            DocumentType::Markdown => {
+                let (text, encoding) = read_text(&path).with_context(context)?;
+                let markdown = options.markdown_reader().parse(&text);
+                let fields = markdown.fields().clone();
+                let (content, title, sections) = markdown.into_parts();
+                let builder = builder
+                    .with_encoding(encoding)
+                    .with_title(title)
+                    .with_sections(sections)
+                    .with_fields(fields);
+                (content, builder)
+            }
+            DocumentType::Epub => {
+                let epub = EpubReader::new().read(&path).with_context(context)?;
+                let mut builder = builder
+                    .with_title(epub.title().map(str::to_string))
+                    .with_sections(epub.chapters().to_vec());
+                if let Some(author) = epub.author() {
+                    builder = builder.with_field("author", FieldValue::Text(author.to_string()));
+                }
+                (epub.into_text(), builder)
+            }
+            DocumentType::Docx | DocumentType::Odt => {
+                let format = match document_type {
+                    DocumentType::Docx => OfficeFormat::Docx,
+                    _ => OfficeFormat::Odt,
+                };
+                let office = OfficeReader::new(format)
+                    .read(&path)
+                    .with_context(context)?;
+                let mut builder = builder.with_title(office.title().map(str::to_string));
+                if let Some(author) = office.author() {
+                    builder = builder.with_field("author", FieldValue::Text(author.to_string()));
+                }
+                if let Some(modified) = office.modified() {
+                    builder = builder.with_field("modified", FieldValue::Date(modified));
+                }
+                (office.into_text(), builder)
+            }
+            DocumentType::Code(language) if options.code_fields => {
+                let (source, encoding) = read_text(&path).with_context(context)?;
+                let (content, comments, strings) =
+                    CodeReader::new(language).parse(&source).into_parts();
+                text_fields.push((COMMENTS_FIELD, comments));
+                text_fields.push((STRINGS_FIELD, strings));
+                (content, builder.with_encoding(encoding))
+            }
+            DocumentType::Text | DocumentType::Json | DocumentType::Code(_) => {
+                let (content, encoding) = read_text(&path).with_context(context)?;
+                (content, builder.with_encoding(encoding))
+            }
+            _ => (read_to_string(&path).with_context(context)?, builder),
+        };
+        let builder = with_file_fields(builder, &path);
+        let builder = with_content_fields(builder, &content, document_type);
+        let metadata = builder.with_source(DocumentSource::File(path)).build();
+        let mut document = Document::new(doc_id, content, metadata);
+        for (name, text) in text_fields {
+            document = document.with_text_field(name, text);
+        }
+        Ok(document)
+    }
+
+    /// Creates a document from a record of a file that holds many
+    /// records, e.g. a line of a JSONL file. The fields of the record
+    /// become the fields of the document, along with the fields that
+    /// are extracted from its text.
+    pub fn from_record(doc_id: DocumentId, path: impl AsRef<Path>, record: Record) -> Self {
+        let path = path.as_ref().to_path_buf();
+        let (key, content, fields) = record.into_parts();
+        let mut builder = DocumentMetadataBuilder::new().with_fields(fields);
+        if let Some(document_type) = DocumentType::from_path(&path) {
+            builder = builder.with_document_type(document_type);
+            builder = with_content_fields(builder, &content, document_type);
+        }
+        let metadata = builder
+            .with_source(DocumentSource::Record { path, key })
             .build();
-        Ok(Document::new(doc_id, content, metadata))
+        Document::new(doc_id, content, metadata)
+    }
+
+    /// Creates a passage of the document with the specified document ID,
+    /// whose content is the specified byte range of the document's content.
+    /// The passage has the metadata of the document, but not its text fields.
+    pub fn passage(&self, doc_id: DocumentId, range: Range<usize>) -> Self {
+        let content = self.page_content[range].to_string();
+        Document::new(doc_id, content, self.metadata.clone())
     }
 
     /// Returns the document ID
@@ -51,4 +176,57 @@ impl Document {
     pub fn page_content(&self) -> &str {
         &self.page_content
     }
+
+    /// Returns the metadata of the document.
+    pub fn metadata(&self) -> &DocumentMetadata {
+        &self.metadata
+    }
+
+    /// Returns the named text fields of the document.
+    pub fn text_fields(&self) -> &BTreeMap<String, String> {
+        &self.text_fields
+    }
+}
+
+/// Adds the fields of the file of a document: the dates that it was
+/// created and last modified, and its size in bytes. The fields that the
+/// file system does not record are omitted.
+fn with_file_fields(mut builder: DocumentMetadataBuilder, path: &Path) -> DocumentMetadataBuilder {
+    if let Ok(created) = archive::created(path) {
+        builder = builder.with_default_field("created", FieldValue::Date(Date::from(created)));
+    }
+    if let Ok(modified) = archive::modified(path) {
+        builder = builder.with_default_field("modified", FieldValue::Date(Date::from(modified)));
+    }
+    if let Ok(size) = archive::size(path) {
+        builder = builder.with_default_field("size", FieldValue::Number(size as f64));
+    }
+    builder
+}
+
+/// Adds the fields that are extracted from the content of a document: its
+/// MIME type, its word count, and the language that it is written in, if
+/// it is detected. Source code has no language.
+fn with_content_fields(
+    mut builder: DocumentMetadataBuilder,
+    content: &str,
+    document_type: DocumentType,
+) -> DocumentMetadataBuilder {
+    let words = content
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .count();
+    builder = builder
+        .with_default_field(
+            "mime",
+            FieldValue::Text(document_type.mime_type().to_string()),
+        )
+        .with_default_field("words", FieldValue::Number(words as f64));
+    if !matches!(document_type, DocumentType::Code(_)) {
+        if let Some(language) = language::detect(content) {
+            builder =
+                builder.with_default_field("language", FieldValue::Text(language.to_string()));
+        }
+    }
+    builder
 }
@@ -1,14 +1,115 @@
 use std::ffi::OsStr;
+use std::io::{self, Read};
 use std::path::Path;
 
-#[derive(Debug, PartialEq)]
+use crate::core::sniff::{sniff, ContentKind, SNIFF_LEN};
+use crate::read::compress::{self, strip_compression};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DocumentType {
     Xml,
     Text,
+    Markdown,
     Pdf,
+    Epub,
+    Docx,
+    Odt,
+    Jsonl,
+    Csv,
+    Json,
+    Trec,
+    MediaWiki,
+    Code(CodeLanguage),
+}
+
+/// The programming language of a source code document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeLanguage {
+    Rust,
+    Python,
+    Go,
+    JavaScript,
+    TypeScript,
+    Java,
+    Kotlin,
+    Swift,
+    CSharp,
+    C,
+    Cpp,
+    Ruby,
+    Shell,
+}
+
+impl CodeLanguage {
+    /// Returns the programming language based on the extension of
+    /// the source file.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext {
+            "rs" => Some(Self::Rust),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "tsx" => Some(Self::TypeScript),
+            "java" => Some(Self::Java),
+            "kt" | "kts" => Some(Self::Kotlin),
+            "swift" => Some(Self::Swift),
+            "cs" => Some(Self::CSharp),
+            "c" | "h" => Some(Self::C),
+            "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => Some(Self::Cpp),
+            "rb" => Some(Self::Ruby),
+            "sh" | "bash" | "zsh" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    /// Returns the MIME type of the source files of the language.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Rust => "text/x-rust",
+            Self::Python => "text/x-python",
+            Self::Go => "text/x-go",
+            Self::JavaScript => "text/javascript",
+            Self::TypeScript => "text/x-typescript",
+            Self::Java => "text/x-java",
+            Self::Kotlin => "text/x-kotlin",
+            Self::Swift => "text/x-swift",
+            Self::CSharp => "text/x-csharp",
+            Self::C => "text/x-c",
+            Self::Cpp => "text/x-c++",
+            Self::Ruby => "text/x-ruby",
+            Self::Shell => "application/x-sh",
+        }
+    }
 }
 
 impl DocumentType {
+    /// Returns the MIME type of the documents of the type.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Xml => "application/xml",
+            Self::Text => "text/plain",
+            Self::Markdown => "text/markdown",
+            Self::Pdf => "application/pdf",
+            Self::Epub => "application/epub+zip",
+            Self::Docx => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            Self::Odt => "application/vnd.oasis.opendocument.text",
+            Self::Jsonl => "application/x-ndjson",
+            Self::Csv => "text/csv",
+            Self::Json => "application/json",
+            Self::Trec => "application/sgml",
+            Self::MediaWiki => "application/xml",
+            Self::Code(language) => language.mime_type(),
+        }
+    }
+
+    /// Returns true if the content of the documents of the type keeps the
+    /// lines of their files, i.e. of plain text, JSON that is read as text,
+    /// and source code, so that the lines of the content are the lines of
+    /// the file.
+    pub fn keeps_lines(&self) -> bool {
+        matches!(self, Self::Text | Self::Json | Self::Code(_))
+    }
+
     /// Returns the file type based on the extension of the file.
     ///
     /// # Examples
@@ -24,14 +125,28 @@ impl DocumentType {
     pub fn from_extension(ext: &OsStr) -> Option<Self> {
         match ext.to_str().unwrap() {
             "xhtml" | "html" | "xml" => Some(Self::Xml),
-            "txt" | "md" => Some(Self::Text),
+            "txt" => Some(Self::Text),
+            "md" | "markdown" => Some(Self::Markdown),
             "pdf" => Some(Self::Pdf),
-            _ => None,
+            "epub" => Some(Self::Epub),
+            "docx" => Some(Self::Docx),
+            "odt" => Some(Self::Odt),
+            "jsonl" | "ndjson" => Some(Self::Jsonl),
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            "trec" | "sgml" => Some(Self::Trec),
+            ext => CodeLanguage::from_extension(ext).map(Self::Code),
         }
     }
 
     /// Returns the file type based on the path of the file.
     ///
+    /// Compressed files, e.g. `notes.md.gz`, have the type of the file
+    /// that they compress. XML files that are named like MediaWiki dumps,
+    /// e.g. `enwiki-latest-pages-articles.xml`, are MediaWiki dumps. Files
+    /// without an extension have no type by their path, but may be text
+    /// files by their contents, see `from_contents`.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -43,12 +158,72 @@ impl DocumentType {
     /// assert_eq!(file_type, Some(DocumentType::Text));
     /// ```
     pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
-        let extension = path.as_ref().extension();
-        match extension {
-            Some(ext) => Self::from_extension(ext),
-            None => None,
+        let path = strip_compression(path);
+        match Self::from_extension(path.extension()?)? {
+            Self::Xml if is_mediawiki_dump(&path) => Some(Self::MediaWiki),
+            document_type => Some(document_type),
+        }
+    }
+
+    /// Returns the file type based on the path of the file and the first
+    /// bytes of its contents. The type of the path is kept only if the
+    /// contents agree with it, so binary files are never text files, e.g.
+    /// an image named `notes.txt`, and PDF documents must start like PDF
+    /// documents. Files without an extension, e.g. `README`, are text files
+    /// if their contents are text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use documents::DocumentType;
+    ///
+    /// let file_type = DocumentType::from_contents("LICENSE", b"MIT License");
+    /// assert_eq!(file_type, Some(DocumentType::Text));
+    /// let file_type = DocumentType::from_contents("notes.txt", b"\x89PNG\r\n\x1a\n");
+    /// assert_eq!(file_type, None);
+    /// ```
+    pub fn from_contents(path: impl AsRef<Path>, head: &[u8]) -> Option<Self> {
+        let document_type = Self::from_path_or_text(path.as_ref())?;
+        match (document_type, sniff(head)) {
+            (Self::Pdf, ContentKind::Pdf) => Some(document_type),
+            (Self::Epub | Self::Docx | Self::Odt, ContentKind::Zip) => Some(document_type),
+            (Self::Pdf | Self::Epub | Self::Docx | Self::Odt, _) => None,
+            (_, ContentKind::Text) => Some(document_type),
+            _ => None,
         }
     }
+
+    /// Detects the type of the file at the specified path from its path
+    /// and the first bytes of its contents, as in `from_contents`.
+    /// Compressed files and members of archives are detected by the
+    /// contents that they compress.
+    pub fn detect(path: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        let path = path.as_ref();
+        if Self::from_path_or_text(path).is_none() {
+            return Ok(None);
+        }
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        compress::open(path)?
+            .take(SNIFF_LEN as u64)
+            .read_to_end(&mut head)?;
+        Ok(Self::from_contents(path, &head))
+    }
+
+    /// Returns the file type based on the path of the file, or the text type
+    /// if the file has no extension, which its contents must then agree with.
+    pub(crate) fn from_path_or_text(path: &Path) -> Option<Self> {
+        match strip_compression(path).extension() {
+            Some(_) => Self::from_path(path),
+            None => Some(Self::Text),
+        }
+    }
+}
+
+/// Checks if the name of a file is the name of a MediaWiki dump.
+fn is_mediawiki_dump(path: &Path) -> bool {
+    path.file_stem()
+        .and_then(OsStr::to_str)
+        .is_some_and(|stem| stem.contains("-pages-articles") || stem.contains("-pages-meta"))
 }
 
 #[cfg(test)]
@@ -67,13 +242,96 @@ mod tests {
         );
         assert_eq!(
             DocumentType::from_extension(OsStr::new("md")),
-            Some(DocumentType::Text)
+            Some(DocumentType::Markdown)
         );
         assert_eq!(
             DocumentType::from_extension(OsStr::new("pdf")),
             Some(DocumentType::Pdf)
         );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("epub")),
+            Some(DocumentType::Epub)
+        );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("docx")),
+            Some(DocumentType::Docx)
+        );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("odt")),
+            Some(DocumentType::Odt)
+        );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("jsonl")),
+            Some(DocumentType::Jsonl)
+        );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("csv")),
+            Some(DocumentType::Csv)
+        );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("rs")),
+            Some(DocumentType::Code(CodeLanguage::Rust))
+        );
+        assert_eq!(
+            DocumentType::from_extension(OsStr::new("trec")),
+            Some(DocumentType::Trec)
+        );
         assert_eq!(DocumentType::from_extension(OsStr::new("")), None);
         assert_eq!(DocumentType::from_extension(OsStr::new("jpg")), None);
     }
+
+    #[test]
+    fn test_file_type_from_path() {
+        assert_eq!(
+            DocumentType::from_path("dumps/enwiki-latest-pages-articles.xml"),
+            Some(DocumentType::MediaWiki)
+        );
+        assert_eq!(
+            DocumentType::from_path("pages/index.xml"),
+            Some(DocumentType::Xml)
+        );
+        assert_eq!(
+            DocumentType::from_path("notes.md.gz"),
+            Some(DocumentType::Markdown)
+        );
+        assert_eq!(
+            DocumentType::from_path("enwiki-latest-pages-articles.xml.bz2"),
+            Some(DocumentType::MediaWiki)
+        );
+        assert_eq!(DocumentType::from_path("image.jpg.gz"), None);
+        assert_eq!(DocumentType::from_path("README"), None);
+    }
+
+    #[test]
+    fn test_file_type_from_contents() {
+        assert_eq!(
+            DocumentType::from_contents("NOTES", b"Buy milk.\n"),
+            Some(DocumentType::Text)
+        );
+        assert_eq!(
+            DocumentType::from_contents("main.rs", b"fn main() {}\n"),
+            Some(DocumentType::Code(CodeLanguage::Rust))
+        );
+        assert_eq!(
+            DocumentType::from_contents("book.epub", b"PK\x03\x04\x14\x00"),
+            Some(DocumentType::Epub)
+        );
+        assert_eq!(
+            DocumentType::from_contents("paper.pdf", b"%PDF-1.7\n"),
+            Some(DocumentType::Pdf)
+        );
+        assert_eq!(DocumentType::from_contents("paper.pdf", b"Not a PDF"), None);
+        assert_eq!(
+            DocumentType::from_contents("notes.txt", b"PK\x03\x04"),
+            None
+        );
+        assert_eq!(
+            DocumentType::from_contents("a.out", b"\x7FELF\x02\x01\x01\x00"),
+            None
+        );
+        assert_eq!(
+            DocumentType::from_contents("program", b"\x7FELF\x02\x01\x01\x00"),
+            None
+        );
+    }
 }
@@ -0,0 +1,247 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// A typed value of a document metadata field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FieldValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+    Date(Date),
+    List(Vec<String>),
+}
+
+impl FieldValue {
+    /// Returns the value as a string slice, if it is text.
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a number, if it is a number.
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Self::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a date, if it is a date.
+    pub fn as_date(&self) -> Option<Date> {
+        match self {
+            Self::Date(date) => Some(*date),
+            _ => None,
+        }
+    }
+
+    /// Returns the value as a list of strings, if it is a list.
+    pub fn as_list(&self) -> Option<&[String]> {
+        match self {
+            Self::List(list) => Some(list),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Bool(bool) => write!(f, "{bool}"),
+            Self::Date(date) => write!(f, "{date}"),
+            Self::List(list) => write!(f, "{}", list.join(", ")),
+        }
+    }
+}
+
+/// A calendar date, without time of day.
+///
+/// Dates are ordered chronologically, and are written and parsed in
+/// the `YYYY-MM-DD` format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct Date {
+    year: i32,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Creates a new date. If the month is out of range, or the day is not
+    /// a day of the month in that year, e.g. February 30, `None` is returned.
+    pub fn new(year: i32, month: u8, day: u8) -> Option<Self> {
+        if (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the year of the date.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// Returns the month of the date, from 1 to 12.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, from 1 to 31.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+}
+
+/// Returns the number of days of a month, from 1 to 12, in a year of the
+/// Gregorian calendar.
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Ord for Date {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.year, self.month, self.day).cmp(&(other.year, other.month, other.day))
+    }
+}
+
+impl PartialOrd for Date {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// The error returned when a string cannot be parsed as a date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDateError(String);
+
+impl fmt::Display for ParseDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid date: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDateError {}
+
+impl FromStr for Date {
+    type Err = ParseDateError;
+
+    /// Parses a date in the `YYYY-MM-DD` format. A time of day may
+    /// follow the date, separated by `T` or a space, and is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDateError(s.to_string());
+        let date = s.trim().split(['T', ' ']).next().ok_or_else(err)?;
+        let mut parts = date.splitn(3, '-');
+        let (year, month, day) = (parts.next(), parts.next(), parts.next());
+        match (year, month, day) {
+            (Some(year), Some(month), Some(day))
+                if year.len() == 4 && month.len() == 2 && day.len() == 2 =>
+            {
+                let year = year.parse().map_err(|_| err())?;
+                let month = month.parse().map_err(|_| err())?;
+                let day = day.parse().map_err(|_| err())?;
+                Date::new(year, month, day).ok_or_else(err)
+            }
+            _ => Err(err()),
+        }
+    }
+}
+
+impl From<Date> for String {
+    fn from(date: Date) -> Self {
+        date.to_string()
+    }
+}
+
+impl From<SystemTime> for Date {
+    /// Converts a point in time into the date that it falls on, in UTC.
+    fn from(time: SystemTime) -> Self {
+        let secs = match time.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs() as i64,
+            Err(err) => -(err.duration().as_secs() as i64),
+        };
+        // Converts the days since the epoch into a date of the proleptic
+        // Gregorian calendar, in eras of 400 years that start on March 1st.
+        let days = secs.div_euclid(86_400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u8;
+        let year = (year_of_era + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+}
+
+impl TryFrom<String> for Date {
+    type Error = ParseDateError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_date() {
+        assert_eq!("2024-03-09".parse(), Ok(Date::new(2024, 3, 9).unwrap()));
+        assert_eq!(
+            "2024-03-09T10:00:00Z".parse(),
+            Ok(Date::new(2024, 3, 9).unwrap())
+        );
+        assert!("2024-13-09".parse::<Date>().is_err());
+        assert!("2024-02-31".parse::<Date>().is_err());
+        assert!("2023-04-31".parse::<Date>().is_err());
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert_eq!(Date::new(2024, 2, 29).map(|date| date.day()), Some(29));
+        assert_eq!(Date::new(1900, 2, 29), None);
+        assert!(Date::new(2000, 2, 29).is_some());
+        assert!("2024-3-9".parse::<Date>().is_err());
+        assert!("yesterday".parse::<Date>().is_err());
+    }
+
+    #[test]
+    fn test_date_order() {
+        let earlier: Date = "2023-12-31".parse().unwrap();
+        let later: Date = "2024-01-01".parse().unwrap();
+        assert!(earlier < later);
+        assert_eq!(later.to_string(), "2024-01-01");
+    }
+
+    #[test]
+    fn test_date_from_system_time() {
+        let day = std::time::Duration::from_secs(86_400);
+        assert_eq!(Date::from(UNIX_EPOCH).to_string(), "1970-01-01");
+        assert_eq!(Date::from(UNIX_EPOCH - day).to_string(), "1969-12-31");
+        let leap_day = UNIX_EPOCH + day * 19_782;
+        assert_eq!(Date::from(leap_day).to_string(), "2024-02-29");
+    }
+}
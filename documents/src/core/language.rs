@@ -0,0 +1,132 @@
+//! Detection of the natural language that documents are written in. The
+//! languages are those of the tokenizer, so that a detected language is the
+//! language that the document is stemmed in.
+
+pub use tokenize::Language;
+
+/// The number of words at the start of a text that its language is
+/// detected from.
+const DETECT_WORDS: usize = 2000;
+
+/// The least number of marker words of a language that a text must have
+/// to be detected as written in it.
+const MIN_MARKERS: usize = 3;
+
+/// The languages that are detected, i.e. those whose function words are
+/// known.
+pub const DETECTED: [Language; 7] = [
+    Language::English,
+    Language::German,
+    Language::French,
+    Language::Spanish,
+    Language::Italian,
+    Language::Portuguese,
+    Language::Dutch,
+];
+
+/// Returns the most frequent function words of the language, which
+/// mark the texts written in it.
+fn markers(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::English => &[
+            "the", "and", "of", "to", "is", "that", "with", "for", "this", "are", "was", "it",
+            "not", "be", "have",
+        ],
+        Language::German => &[
+            "der", "die", "und", "das", "ist", "nicht", "ein", "eine", "mit", "den", "auch",
+            "sich", "von", "zu", "dem",
+        ],
+        Language::French => &[
+            "le", "la", "les", "et", "des", "est", "une", "dans", "que", "pour", "pas", "du",
+            "qui", "sur", "avec",
+        ],
+        Language::Spanish => &[
+            "el", "la", "los", "las", "y", "que", "del", "es", "en", "por", "una", "para", "con",
+            "no", "se",
+        ],
+        Language::Italian => &[
+            "il", "di", "che", "e", "la", "per", "non", "una", "sono", "del", "della", "con",
+            "gli", "è", "anche",
+        ],
+        Language::Portuguese => &[
+            "o", "a", "os", "de", "que", "e", "do", "da", "não", "uma", "para", "com", "em", "é",
+            "por",
+        ],
+        Language::Dutch => &[
+            "de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te", "zijn", "met",
+            "voor", "die", "ook",
+        ],
+        _ => &[],
+    }
+}
+
+/// Detects the language that a text is written in, from the function
+/// words that its first words include. If the text has too few of them,
+/// or they are as frequent in two languages, `None` is returned.
+///
+/// # Examples
+///
+/// ```
+/// use documents::core::language::{detect, Language};
+///
+/// let text = "The index is built from the words of the documents.";
+/// assert_eq!(detect(text), Some(Language::English));
+/// let text = "Der Index wird aus den Wörtern der Dokumente erstellt, und ist schnell.";
+/// assert_eq!(detect(text), Some(Language::German));
+/// assert_eq!(detect("fn main() {}"), None);
+/// ```
+pub fn detect(text: &str) -> Option<Language> {
+    let mut counts = [0; DETECTED.len()];
+    let words = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|word| !word.is_empty())
+        .take(DETECT_WORDS);
+    for word in words {
+        let word = word.to_lowercase();
+        for (count, language) in counts.iter_mut().zip(DETECTED) {
+            if markers(language).contains(&word.as_str()) {
+                *count += 1;
+            }
+        }
+    }
+    let mut ranked = counts.into_iter().zip(DETECTED).collect::<Vec<_>>();
+    ranked.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+    match ranked.as_slice() {
+        [(best, language), (second, _), ..] if *best >= MIN_MARKERS && best > second => {
+            Some(*language)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        let cases = [
+            (
+                "Le moteur de recherche est rapide et il indexe les documents dans le dossier.",
+                Language::French,
+            ),
+            (
+                "El motor de búsqueda es rápido y los documentos se indexan en la carpeta.",
+                Language::Spanish,
+            ),
+            (
+                "Il motore di ricerca è veloce e indicizza i documenti della cartella per te.",
+                Language::Italian,
+            ),
+            (
+                "De zoekmachine is snel en het indexeert de documenten van een map.",
+                Language::Dutch,
+            ),
+        ];
+        for (text, language) in cases {
+            assert_eq!(detect(text), Some(language), "{text}");
+        }
+        assert_eq!(detect("the the"), None);
+        assert_eq!(Language::from_code("PT"), Some(Language::Portuguese));
+    }
+}
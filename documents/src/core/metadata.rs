@@ -1,11 +1,124 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
+use crate::core::dtype::DocumentType;
+use crate::core::field::{Date, FieldValue};
+use crate::core::language::Language;
+use crate::read::Encoding;
+
 /// Structure that stores metadata for a document.
 /// The metadata could be the document source, the source
 /// type, etc.
+///
+/// Named fields, such as the title, the author or the tags of
+/// the document, are stored as typed values by their lowercase
+/// name. The fields that are extracted from every document are
+/// its `created` and `modified` dates, its `size` in bytes, its
+/// `mime` type, its `words` count and its detected `language`.
 #[derive(Default, Debug, Clone)]
 pub struct DocumentMetadata {
     source: Option<DocumentSource>,
+    document_type: Option<DocumentType>,
+    encoding: Option<Encoding>,
+    sections: Vec<Section>,
+    fields: BTreeMap<String, FieldValue>,
+}
+
+impl DocumentMetadata {
+    /// Returns the source of the document, if known.
+    pub fn source(&self) -> Option<&DocumentSource> {
+        self.source.as_ref()
+    }
+
+    /// Returns the type of the document, if known.
+    pub fn document_type(&self) -> Option<DocumentType> {
+        self.document_type
+    }
+
+    /// Returns the character encoding that the text file of the document
+    /// was read from, if it is a text file.
+    pub fn encoding(&self) -> Option<Encoding> {
+        self.encoding
+    }
+
+    /// Returns the title of the document, if known.
+    pub fn title(&self) -> Option<&str> {
+        self.field("title")?.as_text()
+    }
+
+    /// Returns the author of the document, if known.
+    pub fn author(&self) -> Option<&str> {
+        self.field("author")?.as_text()
+    }
+
+    /// Returns the date of the document, if known.
+    pub fn date(&self) -> Option<Date> {
+        self.field("date")?.as_date()
+    }
+
+    /// Returns the date that the document was created, if known.
+    pub fn created(&self) -> Option<Date> {
+        self.field("created")?.as_date()
+    }
+
+    /// Returns the date that the document was last modified, if known.
+    pub fn modified(&self) -> Option<Date> {
+        self.field("modified")?.as_date()
+    }
+
+    /// Returns the size of the document's file in bytes, if known.
+    pub fn size(&self) -> Option<u64> {
+        Some(self.field("size")?.as_number()? as u64)
+    }
+
+    /// Returns the MIME type of the document, if known.
+    pub fn mime_type(&self) -> Option<&str> {
+        self.field("mime")?.as_text()
+    }
+
+    /// Returns the number of words of the document's content, if known.
+    pub fn word_count(&self) -> Option<usize> {
+        Some(self.field("words")?.as_number()? as usize)
+    }
+
+    /// Returns the language that the document is written in, if it was
+    /// detected.
+    pub fn language(&self) -> Option<Language> {
+        Language::from_code(self.field("language")?.as_text()?)
+    }
+
+    /// Returns the tags of the document.
+    pub fn tags(&self) -> &[String] {
+        self.field("tags")
+            .and_then(FieldValue::as_list)
+            .unwrap_or_default()
+    }
+
+    /// Returns the value of the field with the specified name, if any.
+    pub fn field(&self, name: &str) -> Option<&FieldValue> {
+        self.fields.get(name)
+    }
+
+    /// Returns all the named fields of the document.
+    pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+        &self.fields
+    }
+
+    /// Returns the sections of the document, in the order they appear.
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Returns the section that contains the specified byte offset of
+    /// the document's content, i.e. the last section that starts at or
+    /// before the offset. If the offset precedes the first section,
+    /// `None` is returned.
+    pub fn section_at(&self, offset: usize) -> Option<&Section> {
+        let idx = self.sections.partition_point(|s| s.offset <= offset);
+        idx.checked_sub(1).map(|idx| &self.sections[idx])
+    }
 }
 
 #[derive(Default, Debug)]
@@ -29,6 +142,66 @@ impl DocumentMetadataBuilder {
         }
     }
 
+    /// Sets the document type.
+    pub fn with_document_type(self, document_type: DocumentType) -> Self {
+        DocumentMetadataBuilder {
+            meta: DocumentMetadata {
+                document_type: Some(document_type),
+                ..self.meta
+            },
+        }
+    }
+
+    /// Sets the character encoding of the document's file.
+    pub fn with_encoding(self, encoding: Encoding) -> Self {
+        DocumentMetadataBuilder {
+            meta: DocumentMetadata {
+                encoding: Some(encoding),
+                ..self.meta
+            },
+        }
+    }
+
+    /// Sets the document title.
+    pub fn with_title(self, title: Option<String>) -> Self {
+        match title {
+            Some(title) => self.with_field("title", FieldValue::Text(title)),
+            None => self,
+        }
+    }
+
+    /// Sets the value of a named field. The name is lowercased.
+    pub fn with_field(mut self, name: &str, value: FieldValue) -> Self {
+        self.meta.fields.insert(name.to_lowercase(), value);
+        self
+    }
+
+    /// Sets the value of a named field, unless the field is already set.
+    /// The name is lowercased.
+    pub fn with_default_field(mut self, name: &str, value: FieldValue) -> Self {
+        self.meta.fields.entry(name.to_lowercase()).or_insert(value);
+        self
+    }
+
+    /// Sets the values of multiple named fields, replacing the values
+    /// of fields with the same name.
+    pub fn with_fields(self, fields: impl IntoIterator<Item = (String, FieldValue)>) -> Self {
+        fields.into_iter().fold(self, |builder, (name, value)| {
+            builder.with_field(&name, value)
+        })
+    }
+
+    /// Sets the document sections. The sections are expected to be
+    /// ordered by their offset.
+    pub fn with_sections(self, sections: Vec<Section>) -> Self {
+        DocumentMetadataBuilder {
+            meta: DocumentMetadata {
+                sections,
+                ..self.meta
+            },
+        }
+    }
+
     /// Consumes the document metadata builder and returns the metadata.
     pub fn build(self) -> DocumentMetadata {
         self.meta
@@ -38,4 +211,88 @@ impl DocumentMetadataBuilder {
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum DocumentSource {
     File(PathBuf),
+    /// A record of a file that holds many records, e.g. a line of a
+    /// JSONL file, identified by its key within the file.
+    Record {
+        path: PathBuf,
+        key: String,
+    },
+}
+
+/// A section of a document, marked by a heading.
+///
+/// The offset is the byte offset in the document's content where
+/// the section's heading starts.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Section {
+    heading: String,
+    level: u8,
+    offset: usize,
+}
+
+impl Section {
+    /// Creates a new section with the specified heading, heading level
+    /// and byte offset.
+    pub fn new(heading: String, level: u8, offset: usize) -> Self {
+        Self {
+            heading,
+            level,
+            offset,
+        }
+    }
+
+    /// Returns the heading of the section.
+    pub fn heading(&self) -> &str {
+        &self.heading
+    }
+
+    /// Returns the level of the heading, starting from 1.
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Returns the byte offset where the section starts.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_section_at() {
+        let metadata = DocumentMetadataBuilder::new()
+            .with_sections(vec![
+                Section::new("Intro".to_string(), 1, 10),
+                Section::new("Usage".to_string(), 2, 50),
+            ])
+            .build();
+        assert_eq!(metadata.section_at(0), None);
+        assert_eq!(metadata.section_at(10).unwrap().heading(), "Intro");
+        assert_eq!(metadata.section_at(49).unwrap().heading(), "Intro");
+        assert_eq!(metadata.section_at(120).unwrap().heading(), "Usage");
+    }
+
+    #[test]
+    fn test_fields() {
+        let metadata = DocumentMetadataBuilder::new()
+            .with_title(Some("Notes".to_string()))
+            .with_field("Author", FieldValue::Text("Ada".to_string()))
+            .with_field("tags", FieldValue::List(vec!["rust".to_string()]))
+            .build();
+        assert_eq!(metadata.title(), Some("Notes"));
+        assert_eq!(metadata.author(), Some("Ada"));
+        assert_eq!(metadata.tags(), ["rust".to_string()]);
+        assert_eq!(metadata.date(), None);
+
+        let metadata = DocumentMetadataBuilder::new()
+            .with_field("words", FieldValue::Number(120.0))
+            .with_default_field("words", FieldValue::Number(5.0))
+            .with_default_field("language", FieldValue::Text("de".to_string()))
+            .build();
+        assert_eq!(metadata.word_count(), Some(120));
+        assert_eq!(metadata.language(), Some(Language::German));
+    }
 }
@@ -1,3 +1,6 @@
 pub mod document;
 pub mod dtype;
+pub mod field;
+pub mod language;
 pub mod metadata;
+pub mod sniff;
@@ -0,0 +1,84 @@
+use crate::read::encoding;
+
+/// The number of bytes at the start of a file that its contents are
+/// detected from.
+pub const SNIFF_LEN: usize = 8192;
+
+/// The signatures of binary file formats that are never read as text.
+const BINARY_SIGNATURES: [&[u8]; 10] = [
+    b"\x89PNG\r\n\x1a\n",
+    b"\xFF\xD8\xFF",
+    b"GIF87a",
+    b"GIF89a",
+    b"\x7FELF",
+    b"\xCF\xFA\xED\xFE",
+    b"\xCE\xFA\xED\xFE",
+    b"SQLite format 3\x00",
+    b"\x1F\x8B",
+    b"7z\xBC\xAF\x27\x1C",
+];
+
+/// The kind of the contents of a file, as told by its first bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentKind {
+    /// Text in one of the encodings that are read, e.g. UTF-8.
+    Text,
+    /// A PDF document.
+    Pdf,
+    /// A zip container, e.g. an EPUB or a DOCX document.
+    Zip,
+    /// Any other binary data, e.g. an image or an executable.
+    Binary,
+}
+
+/// Detects the kind of the contents of a file from its first bytes,
+/// by the signatures of known formats, or else by whether the bytes
+/// are text.
+///
+/// # Examples
+///
+/// ```
+/// use documents::core::sniff::{sniff, ContentKind};
+///
+/// assert_eq!(sniff(b"%PDF-1.7\n"), ContentKind::Pdf);
+/// assert_eq!(sniff(b"# Notes\n"), ContentKind::Text);
+/// assert_eq!(sniff(b"\x7FELF\x02\x01\x01\x00"), ContentKind::Binary);
+/// ```
+pub fn sniff(head: &[u8]) -> ContentKind {
+    if head.starts_with(b"%PDF-") {
+        return ContentKind::Pdf;
+    }
+    if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+        return ContentKind::Zip;
+    }
+    if BINARY_SIGNATURES.iter().any(|sig| head.starts_with(sig)) {
+        return ContentKind::Binary;
+    }
+    // The bytes are cut at a boundary of UTF-16 code units, since the
+    // head of a larger file may end in the middle of one.
+    let head = match encoding::has_bom(head) {
+        true => head,
+        false => &head[..head.len() - head.len() % 2],
+    };
+    match encoding::decode(head) {
+        Ok(_) => ContentKind::Text,
+        Err(_) => ContentKind::Binary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(sniff(b""), ContentKind::Text);
+        assert_eq!(sniff(b"Copyright (c) 2024\n"), ContentKind::Text);
+        assert_eq!(sniff(b"\xFF\xFEh\x00i\x00"), ContentKind::Text);
+        assert_eq!(sniff(b"h\x00i\x00!"), ContentKind::Text);
+        assert_eq!(sniff(b"PK\x03\x04\x14\x00"), ContentKind::Zip);
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\x00\x00"), ContentKind::Binary);
+        assert_eq!(sniff(b"GIF89a"), ContentKind::Binary);
+        assert_eq!(sniff(b"\x00\x01\x02\x03\xFF"), ContentKind::Binary);
+    }
+}
@@ -1,7 +1,9 @@
 pub use crate::core::document::Document;
-pub use crate::core::dtype::DocumentType;
-pub use crate::core::metadata::{DocumentMetadata, DocumentSource};
-pub use crate::read::read_to_string;
+pub use crate::core::dtype::{CodeLanguage, DocumentType};
+pub use crate::core::field::{Date, FieldValue};
+pub use crate::core::language::Language;
+pub use crate::core::metadata::{DocumentMetadata, DocumentSource, Section};
+pub use crate::read::{read_to_string, ReadOptions};
 
 pub type DocumentId = u32;
 
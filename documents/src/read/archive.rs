@@ -0,0 +1,537 @@
+use std::cell::RefCell;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use zip::ZipArchive;
+
+use crate::core::dtype::DocumentType;
+use crate::core::sniff::SNIFF_LEN;
+use crate::read::compress::{strip_compression, Compression};
+
+/// The separator between the path of an archive and the path of one of
+/// its members, e.g. `bundle.zip!/docs/intro.md`.
+pub const MEMBER_SEPARATOR: &str = "!/";
+
+/// The format of an archive of documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    Zip,
+    /// A tarball, which may be compressed, e.g. `bundle.tar.gz`.
+    Tar(Option<Compression>),
+}
+
+impl ArchiveFormat {
+    /// Returns the archive format based on the path of the file.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        match path.extension().and_then(OsStr::to_str)? {
+            "zip" => Some(Self::Zip),
+            "tar" => Some(Self::Tar(None)),
+            "tgz" => Some(Self::Tar(Some(Compression::Gzip))),
+            _ => match strip_compression(path).extension()?.to_str()? {
+                "tar" => Some(Self::Tar(Compression::from_path(path))),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// The path of a member of an archive, which is written as the path of
+/// the archive and the path of the member in it, separated by `!/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberPath {
+    archive: PathBuf,
+    member: String,
+}
+
+impl MemberPath {
+    /// Creates the path of the member with the specified name in an archive.
+    pub fn new(archive: impl AsRef<Path>, member: &str) -> Self {
+        Self {
+            archive: archive.as_ref().to_path_buf(),
+            member: member.to_string(),
+        }
+    }
+
+    /// Parses the path of a member of an archive. If the path is not the
+    /// path of a member of an archive, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use documents::read::archive::MemberPath;
+    ///
+    /// let path = MemberPath::parse("bundle.zip!/docs/intro.md").unwrap();
+    /// assert_eq!(path.archive().to_str(), Some("bundle.zip"));
+    /// assert_eq!(path.member(), "docs/intro.md");
+    /// ```
+    pub fn parse(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref().to_str()?;
+        path.match_indices(MEMBER_SEPARATOR)
+            .map(|(idx, _)| (&path[..idx], &path[idx + MEMBER_SEPARATOR.len()..]))
+            .find(|(archive, _)| ArchiveFormat::from_path(archive).is_some())
+            .map(|(archive, member)| Self::new(archive, member))
+    }
+
+    /// Returns the path of the archive.
+    pub fn archive(&self) -> &Path {
+        &self.archive
+    }
+
+    /// Returns the path of the member in the archive.
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+
+    /// Returns the path of the member as a virtual path, e.g.
+    /// `bundle.zip!/docs/intro.md`.
+    pub fn to_path_buf(&self) -> PathBuf {
+        let mut path = self.archive.clone().into_os_string();
+        path.push(MEMBER_SEPARATOR);
+        path.push(&self.member);
+        PathBuf::from(path)
+    }
+
+    /// Returns the size of the contents of the member, in bytes. The size of
+    /// a member of a tar archive is taken from its header, unless the member
+    /// is the one that was read last, see [`TarMember`].
+    pub fn size(&self) -> io::Result<u64> {
+        let size = if let Some(ArchiveFormat::Zip) = ArchiveFormat::from_path(&self.archive) {
+            let mut archive = ZipArchive::new(BufReader::new(File::open(&self.archive)?))?;
+            let size = archive.by_name(&self.member)?.size();
+            Some(size)
+        } else if let Some(size) = TarMember::with_kept(self, |contents| contents.len() as u64) {
+            Some(size)
+        } else {
+            let mut size = None;
+            visit_members(&self.archive, |name, member_size, _| {
+                if name == self.member {
+                    size = Some(member_size);
+                }
+                Ok(size.is_none())
+            })?;
+            size
+        };
+        size.ok_or_else(|| self.not_found())
+    }
+
+    /// Reads the contents of the member. Members of zip archives are read
+    /// without reading the rest of the archive, while the members of tar
+    /// archives are read from the start of the archive, unless the member
+    /// is the one that was read last, see [`TarMember`].
+    pub fn read(&self) -> io::Result<Vec<u8>> {
+        let contents = if let Some(ArchiveFormat::Zip) = ArchiveFormat::from_path(&self.archive) {
+            let mut archive = ZipArchive::new(BufReader::new(File::open(&self.archive)?))?;
+            let mut buffer = Vec::new();
+            archive.by_name(&self.member)?.read_to_end(&mut buffer)?;
+            Some(buffer)
+        } else {
+            TarMember::read(self)?
+        };
+        contents.ok_or_else(|| self.not_found())
+    }
+
+    /// Returns the error that the member is not in the archive.
+    fn not_found(&self) -> io::Error {
+        let message = format!("{} is not in {}", self.member, self.archive.display());
+        io::Error::new(io::ErrorKind::NotFound, message)
+    }
+}
+
+thread_local! {
+    /// The member of a tar archive that was read last on the thread.
+    static TAR_MEMBER: RefCell<Option<TarMember>> = const { RefCell::new(None) };
+}
+
+/// A member of a tar archive, with its contents. A member of a tar archive
+/// cannot be found without reading the members before it, and compressed
+/// archives are decompressed from their start, so the member that was read
+/// last is kept for each thread, until another member is read or its archive
+/// is modified. A file is read more than once when its document is loaded,
+/// e.g. to detect its type and to read its text, and so is the file of a
+/// search result, for its section and its lines.
+///
+/// Only one member is kept, so the members of an archive are loaded in one
+/// pass over the archive with [`stream_members`].
+struct TarMember {
+    path: MemberPath,
+    modified: SystemTime,
+    contents: Vec<u8>,
+}
+
+impl TarMember {
+    /// Keeps the contents of a member as the member that was read last.
+    fn keep(path: &MemberPath, contents: Vec<u8>) -> io::Result<()> {
+        let member = Self {
+            path: path.clone(),
+            modified: path.archive.metadata()?.modified()?,
+            contents,
+        };
+        TAR_MEMBER.set(Some(member));
+        Ok(())
+    }
+
+    /// Calls a function with the contents of the member, if it is the member
+    /// that was read last, and its archive was not modified since.
+    fn with_kept<T>(path: &MemberPath, f: impl FnOnce(&[u8]) -> T) -> Option<T> {
+        let modified = path.archive.metadata().and_then(|m| m.modified()).ok()?;
+        TAR_MEMBER.with_borrow(|kept| {
+            kept.as_ref()
+                .filter(|kept| kept.path == *path && kept.modified == modified)
+                .map(|kept| f(&kept.contents))
+        })
+    }
+
+    /// Reads the contents of the member, from the archive unless it is the
+    /// member that was read last, and keeps them.
+    fn read(path: &MemberPath) -> io::Result<Option<Vec<u8>>> {
+        if let Some(contents) = Self::with_kept(path, <[u8]>::to_vec) {
+            return Ok(Some(contents));
+        }
+        let mut contents = None;
+        visit_members(&path.archive, |name, size, reader| {
+            if name == path.member {
+                let mut buffer = Vec::with_capacity(size as usize);
+                reader.read_to_end(&mut buffer)?;
+                contents = Some(buffer);
+            }
+            Ok(contents.is_none())
+        })?;
+        if let Some(contents) = &contents {
+            Self::keep(path, contents.clone())?;
+        }
+        Ok(contents)
+    }
+}
+
+/// Visits the specified members of an archive in the order they are stored
+/// in it, in one pass over the archive. The function is called with the path
+/// of each member while it is the member that was read last, so reading it,
+/// e.g. with [`MemberPath::read`], does not read the archive again.
+pub fn stream_members(
+    archive: impl AsRef<Path>,
+    members: &HashSet<String>,
+    mut f: impl FnMut(MemberPath),
+) -> io::Result<()> {
+    let archive = archive.as_ref();
+    let is_tar = matches!(
+        ArchiveFormat::from_path(archive),
+        Some(ArchiveFormat::Tar(_))
+    );
+    visit_members(archive, |name, size, reader| {
+        if !members.contains(name) {
+            return Ok(true);
+        }
+        let path = MemberPath::new(archive, name);
+        if is_tar {
+            let mut contents = Vec::with_capacity(size as usize);
+            reader.read_to_end(&mut contents)?;
+            TarMember::keep(&path, contents)?;
+        }
+        f(path);
+        Ok(true)
+    })
+}
+
+/// Returns the paths of the files in the archive, in the order that they
+/// are stored in it.
+pub fn members(archive: impl AsRef<Path>) -> io::Result<Vec<String>> {
+    let mut members = Vec::new();
+    visit_members(archive.as_ref(), |name, _, _| {
+        members.push(name.to_string());
+        Ok(true)
+    })?;
+    Ok(members)
+}
+
+/// Returns the paths of the files in the archive, in the order that they
+/// are stored in it, with the types of document that they are detected to
+/// have from their paths and contents, as in `DocumentType::from_contents`.
+pub fn detect_members(
+    archive: impl AsRef<Path>,
+) -> io::Result<Vec<(String, Option<DocumentType>)>> {
+    let mut members = Vec::new();
+    visit_members(archive.as_ref(), |name, _, member| {
+        let document_type = match DocumentType::from_path_or_text(Path::new(name)) {
+            Some(_) => {
+                let mut reader: Box<dyn Read> = match Compression::from_path(name) {
+                    Some(compression) => compression.decoder(BufReader::new(member))?,
+                    None => Box::new(member),
+                };
+                let mut head = Vec::with_capacity(SNIFF_LEN);
+                reader
+                    .by_ref()
+                    .take(SNIFF_LEN as u64)
+                    .read_to_end(&mut head)?;
+                DocumentType::from_contents(name, &head)
+            }
+            None => None,
+        };
+        members.push((name.to_string(), document_type));
+        Ok(true)
+    })?;
+    Ok(members)
+}
+
+/// Returns the hashes of the contents of the files in the archive, by
+/// their paths in it. The hashes are used to tell which members of an
+/// archive are modified, when the archive is modified.
+pub fn member_hashes(archive: impl AsRef<Path>) -> io::Result<BTreeMap<String, u64>> {
+    let mut hashes = BTreeMap::new();
+    visit_members(archive.as_ref(), |name, _, member| {
+        hashes.insert(name.to_string(), hash_contents(member)?);
+        Ok(true)
+    })?;
+    Ok(hashes)
+}
+
+/// Returns the last time that the file at the specified path was modified.
+/// For members of archives, it is the last time that the archive was
+/// modified.
+pub fn modified(path: impl AsRef<Path>) -> io::Result<SystemTime> {
+    match MemberPath::parse(&path) {
+        Some(member_path) => member_path.archive().metadata()?.modified(),
+        None => path.as_ref().metadata()?.modified(),
+    }
+}
+
+/// Returns the time that the file at the specified path was created, if
+/// the file system records it. For members of archives, it is the time
+/// that the archive was created.
+pub fn created(path: impl AsRef<Path>) -> io::Result<SystemTime> {
+    match MemberPath::parse(&path) {
+        Some(member_path) => member_path.archive().metadata()?.created(),
+        None => path.as_ref().metadata()?.created(),
+    }
+}
+
+/// Returns the size of the file at the specified path, in bytes. For
+/// members of archives, it is the size of their contents.
+pub fn size(path: impl AsRef<Path>) -> io::Result<u64> {
+    match MemberPath::parse(&path) {
+        Some(member_path) => member_path.size(),
+        None => Ok(path.as_ref().metadata()?.len()),
+    }
+}
+
+/// A cache of the hashes of the members of archives, so that each archive
+/// is read once when the hashes of many of its members are needed.
+#[derive(Debug, Default)]
+pub struct MemberHashes {
+    archives: HashMap<PathBuf, BTreeMap<String, u64>>,
+}
+
+impl MemberHashes {
+    /// Creates a new, empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the hash of the contents of the member of an archive at the
+    /// specified path. If the path is not the path of a member of an
+    /// archive, `None` is returned.
+    pub fn get(&mut self, path: impl AsRef<Path>) -> io::Result<Option<u64>> {
+        let Some(member_path) = MemberPath::parse(path) else {
+            return Ok(None);
+        };
+        let archive = member_path.archive().to_path_buf();
+        let hashes = match self.archives.entry(archive) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let hashes = member_hashes(entry.key())?;
+                entry.insert(hashes)
+            }
+        };
+        match hashes.get(member_path.member()) {
+            Some(hash) => Ok(Some(*hash)),
+            None => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in the archive", member_path.member()),
+            )),
+        }
+    }
+}
+
+/// Visits the files of an archive in the order they are stored in it.
+/// The visitor is given the path of each file, its size from its header,
+/// and a reader of its contents, and returns whether to continue with the
+/// next file.
+fn visit_members(
+    archive: &Path,
+    mut visit: impl FnMut(&str, u64, &mut dyn Read) -> io::Result<bool>,
+) -> io::Result<()> {
+    let format = ArchiveFormat::from_path(archive).ok_or_else(|| {
+        let message = format!("{} is not an archive", archive.display());
+        io::Error::new(io::ErrorKind::InvalidInput, message)
+    })?;
+    let reader = BufReader::new(File::open(archive)?);
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = ZipArchive::new(reader)?;
+            for idx in 0..archive.len() {
+                let mut member = archive.by_index(idx)?;
+                if member.is_dir() {
+                    continue;
+                }
+                let name = normalize_member(member.name()).to_string();
+                if !visit(&name, member.size(), &mut member)? {
+                    break;
+                }
+            }
+        }
+        ArchiveFormat::Tar(compression) => {
+            let reader: Box<dyn Read> = match compression {
+                Some(compression) => compression.decoder(reader)?,
+                None => Box::new(reader),
+            };
+            let mut archive = tar::Archive::new(reader);
+            for member in archive.entries()? {
+                let mut member = member?;
+                if !member.header().entry_type().is_file() {
+                    continue;
+                }
+                let name = member.path()?.to_string_lossy().into_owned();
+                if !visit(normalize_member(&name), member.size(), &mut member)? {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Removes the leading `./` of the path of a member of an archive.
+fn normalize_member(name: &str) -> &str {
+    name.trim_start_matches("./")
+}
+
+/// Hashes the contents of a reader with the 64-bit FNV-1a hash, which is
+/// stable across platforms and versions of the standard library.
+fn hash_contents(reader: &mut dyn Read) -> io::Result<u64> {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+    let mut buffer = [0; 8192];
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(hash);
+        }
+        for byte in &buffer[..read] {
+            hash = (hash ^ u64::from(*byte)).wrapping_mul(PRIME);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::*;
+
+    #[test]
+    fn test_archive_format() {
+        assert_eq!(
+            ArchiveFormat::from_path("bundle.zip"),
+            Some(ArchiveFormat::Zip)
+        );
+        assert_eq!(
+            ArchiveFormat::from_path("bundle.tar.gz"),
+            Some(ArchiveFormat::Tar(Some(Compression::Gzip)))
+        );
+        assert_eq!(
+            ArchiveFormat::from_path("bundle.tgz"),
+            Some(ArchiveFormat::Tar(Some(Compression::Gzip)))
+        );
+        assert_eq!(ArchiveFormat::from_path("notes.md.gz"), None);
+    }
+
+    #[test]
+    fn test_member_path() {
+        let path = MemberPath::parse("/data/bundle.tar.gz!/docs/intro.md").unwrap();
+        assert_eq!(path.archive(), Path::new("/data/bundle.tar.gz"));
+        assert_eq!(path.member(), "docs/intro.md");
+        assert_eq!(
+            path.to_path_buf(),
+            PathBuf::from("/data/bundle.tar.gz!/docs/intro.md")
+        );
+        assert_eq!(MemberPath::parse("/data/wow!/intro.md"), None);
+    }
+
+    #[test]
+    fn test_read_members() {
+        let dir = std::env::temp_dir().join(format!("searchine-archive-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let zip_path = dir.join("bundle.zip");
+        let mut zip = ZipWriter::new(File::create(&zip_path).unwrap());
+        zip.add_directory("docs/", SimpleFileOptions::default())
+            .unwrap();
+        for (name, contents) in [("docs/intro.md", "# Intro"), ("notes.txt", "Notes")] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(contents.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let tar_path = dir.join("bundle.tar");
+        let mut tar = tar::Builder::new(File::create(&tar_path).unwrap());
+        for (name, contents) in [("./docs/intro.md", "# Intro"), ("notes.txt", "Notes")] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, contents.as_bytes())
+                .unwrap();
+        }
+        tar.finish().unwrap();
+        drop(tar);
+
+        for archive in [&zip_path, &tar_path] {
+            assert_eq!(members(archive).unwrap(), ["docs/intro.md", "notes.txt"]);
+            assert_eq!(
+                detect_members(archive).unwrap(),
+                [
+                    ("docs/intro.md".to_string(), Some(DocumentType::Markdown)),
+                    ("notes.txt".to_string(), Some(DocumentType::Text)),
+                ]
+            );
+            let member = MemberPath::new(archive, "notes.txt");
+            assert_eq!(member.read().unwrap(), b"Notes");
+            assert_eq!(member.read().unwrap(), b"Notes");
+            assert_eq!(size(member.to_path_buf()).unwrap(), 5);
+            let intro = MemberPath::new(archive, "docs/intro.md");
+            assert_eq!(intro.size().unwrap(), 7);
+            assert_eq!(intro.read().unwrap(), b"# Intro");
+            assert!(MemberPath::new(archive, "missing.txt").read().is_err());
+
+            let wanted = HashSet::from(["notes.txt".to_string(), "docs/intro.md".to_string()]);
+            let mut streamed = Vec::new();
+            stream_members(archive, &wanted, |member| {
+                streamed.push((member.member().to_string(), member.read().unwrap()));
+            })
+            .unwrap();
+            assert_eq!(
+                streamed,
+                [
+                    ("docs/intro.md".to_string(), b"# Intro".to_vec()),
+                    ("notes.txt".to_string(), b"Notes".to_vec()),
+                ]
+            );
+
+            let mut hashes = MemberHashes::new();
+            let intro = hashes.get(MemberPath::new(archive, "docs/intro.md").to_path_buf());
+            let notes = hashes.get(member.to_path_buf());
+            assert_ne!(intro.unwrap(), notes.unwrap());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
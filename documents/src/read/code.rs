@@ -0,0 +1,404 @@
+use crate::core::dtype::CodeLanguage;
+
+/// The name of the text field with the comments of a source file.
+pub const COMMENTS_FIELD: &str = "comments";
+
+/// The name of the text field with the string literals of a source file.
+pub const STRINGS_FIELD: &str = "strings";
+
+/// The lexical syntax of a programming language that is needed to
+/// tell comments and string literals apart from code.
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    /// The kinds of string literals, in the order they are tried, e.g.
+    /// triple quotes before single quotes.
+    quotes: &'static [Quote],
+    raw_strings: Option<RawStrings>,
+    /// Whether single quotes enclose character literals, e.g. `'"'`, which
+    /// are kept in the code.
+    char_literals: bool,
+}
+
+/// A kind of string literal, with its opening and closing quotes.
+#[derive(Debug, Clone, Copy)]
+struct Quote {
+    open: &'static str,
+    close: &'static str,
+    /// Whether the literal may span lines. Other literals end at the end of
+    /// the line, so that a stray quote does not swallow the file.
+    multiline: bool,
+    escape: Escape,
+}
+
+/// How the closing quote of a string literal is escaped in the literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    /// With a backslash, e.g. `"say \"hi\""`.
+    Backslash,
+    /// By doubling it, e.g. `@"say ""hi"""` in C#.
+    Double,
+    /// It cannot be escaped, e.g. in Go raw strings.
+    None,
+}
+
+impl Quote {
+    /// A literal that is closed by the quote that opens it, and that ends
+    /// at the end of the line.
+    const fn line(quote: &'static str) -> Self {
+        Self {
+            open: quote,
+            close: quote,
+            multiline: false,
+            escape: Escape::Backslash,
+        }
+    }
+
+    /// A literal that is closed by the quote that opens it, and that may
+    /// span lines.
+    const fn multiline(quote: &'static str, escape: Escape) -> Self {
+        Self {
+            open: quote,
+            close: quote,
+            multiline: true,
+            escape,
+        }
+    }
+}
+
+/// The raw string literals of a language, whose closing delimiters depend
+/// on their opening ones.
+#[derive(Debug, Clone, Copy)]
+enum RawStrings {
+    /// Rust raw strings, e.g. `r#"say "hi""#`.
+    Rust,
+    /// C++ raw strings, e.g. `R"x(say "hi")x"`.
+    Cpp,
+}
+
+impl RawStrings {
+    /// Returns the length of the opening delimiter of the raw string that
+    /// the text starts with, if any, and its closing delimiter.
+    fn open(self, text: &str) -> Option<(usize, String)> {
+        match self {
+            Self::Rust => {
+                let rest = text.strip_prefix('b').unwrap_or(text).strip_prefix('r')?;
+                let hashes = rest.len() - rest.trim_start_matches('#').len();
+                if !rest[hashes..].starts_with('"') {
+                    return None;
+                }
+                let open_len = text.len() - rest.len() + hashes + 1;
+                Some((open_len, format!("\"{}", "#".repeat(hashes))))
+            }
+            Self::Cpp => {
+                let rest = ["u8R\"", "uR\"", "UR\"", "LR\"", "R\""]
+                    .iter()
+                    .find_map(|prefix| text.strip_prefix(prefix))?;
+                let end = rest.find('(')?;
+                let delimiter = &rest[..end];
+                let invalid = |c: char| c.is_whitespace() || matches!(c, ')' | '\\' | '"');
+                if delimiter.len() > 16 || delimiter.contains(invalid) {
+                    return None;
+                }
+                let open_len = text.len() - rest.len() + end + 1;
+                Some((open_len, format!("){delimiter}\"")))
+            }
+        }
+    }
+}
+
+impl Syntax {
+    fn of(language: CodeLanguage) -> Self {
+        use CodeLanguage::*;
+
+        const DOUBLE: Quote = Quote::line("\"");
+        const SINGLE: Quote = Quote::line("'");
+        const MULTILINE_DOUBLE: Quote = Quote::multiline("\"", Escape::Backslash);
+        const MULTILINE_SINGLE: Quote = Quote::multiline("'", Escape::Backslash);
+        const RAW_SINGLE: Quote = Quote::multiline("'", Escape::None);
+        const TRIPLE: Quote = Quote::multiline("\"\"\"", Escape::Backslash);
+        const TRIPLE_SINGLE: Quote = Quote::multiline("'''", Escape::Backslash);
+        const RAW_TRIPLE: Quote = Quote::multiline("\"\"\"", Escape::None);
+        const BACKTICK: Quote = Quote::multiline("`", Escape::Backslash);
+        const RAW_BACKTICK: Quote = Quote::multiline("`", Escape::None);
+        const VERBATIM: Quote = Quote {
+            open: "@\"",
+            close: "\"",
+            multiline: true,
+            escape: Escape::Double,
+        };
+        const VERBATIM_INTERPOLATED: Quote = Quote {
+            open: "@$\"",
+            ..VERBATIM
+        };
+
+        let (quotes, raw_strings): (&'static [Quote], _) = match language {
+            Rust => (&[MULTILINE_DOUBLE], Some(RawStrings::Rust)),
+            C => (&[DOUBLE], None),
+            Cpp => (&[DOUBLE], Some(RawStrings::Cpp)),
+            Java | Swift => (&[TRIPLE, DOUBLE], None),
+            Kotlin => (&[RAW_TRIPLE, DOUBLE], None),
+            CSharp => (&[RAW_TRIPLE, VERBATIM, VERBATIM_INTERPOLATED, DOUBLE], None),
+            Go => (&[DOUBLE, SINGLE, RAW_BACKTICK], None),
+            JavaScript | TypeScript => (&[DOUBLE, SINGLE, BACKTICK], None),
+            Python => (&[TRIPLE, TRIPLE_SINGLE, DOUBLE, SINGLE], None),
+            Ruby => (&[MULTILINE_DOUBLE, MULTILINE_SINGLE], None),
+            Shell => (&[MULTILINE_DOUBLE, RAW_SINGLE], None),
+        };
+        let (line_comments, block_comment): (&'static [&'static str], _) = match language {
+            Python | Ruby | Shell => (&["#"], None),
+            _ => (&["//"], Some(("/*", "*/"))),
+        };
+        Self {
+            line_comments,
+            block_comment,
+            quotes,
+            raw_strings,
+            char_literals: matches!(language, Rust | C | Cpp | Java | Kotlin | CSharp),
+        }
+    }
+
+    /// Takes the string literal that the text starts with, if any, and
+    /// returns its contents and the text after it. `prev` is the character
+    /// before the text, so that e.g. the `r"` of `for"` is not taken for
+    /// the start of a raw string.
+    fn take_literal<'a>(&self, text: &'a str, prev: Option<char>) -> Option<(&'a str, &'a str)> {
+        let in_word = prev.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        if let Some((open_len, close)) = self
+            .raw_strings
+            .filter(|_| !in_word)
+            .and_then(|raw| raw.open(text))
+        {
+            return Some(take_until(&text[open_len..], &close));
+        }
+        let quote = self.quotes.iter().find(|q| text.starts_with(q.open))?;
+        Some(take_string(&text[quote.open.len()..], quote))
+    }
+}
+
+/// The text of a source file, separated into code, comments and
+/// string literals.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CodeDocument {
+    code: String,
+    comments: String,
+    strings: String,
+}
+
+impl CodeDocument {
+    /// Returns the code, without comments and string literals.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Returns the text of the comments, one comment per line.
+    pub fn comments(&self) -> &str {
+        &self.comments
+    }
+
+    /// Returns the contents of the string literals, one literal per line.
+    /// Literals that span lines keep their line breaks.
+    pub fn strings(&self) -> &str {
+        &self.strings
+    }
+
+    /// Consumes the document and returns the code, the comments and the
+    /// string literals.
+    pub fn into_parts(self) -> (String, String, String) {
+        (self.code, self.comments, self.strings)
+    }
+}
+
+/// A reader that separates the comments and string literals of source
+/// files from their code.
+#[derive(Debug, Clone)]
+pub struct CodeReader {
+    language: CodeLanguage,
+}
+
+impl CodeReader {
+    /// Creates a new reader for source files of the specified language.
+    pub fn new(language: CodeLanguage) -> Self {
+        Self { language }
+    }
+
+    /// Separates a source string into code, comments and string literals.
+    pub fn parse(&self, source: &str) -> CodeDocument {
+        let syntax = Syntax::of(self.language);
+        let mut doc = CodeDocument::default();
+        let mut rest = source;
+
+        while let Some(c) = rest.chars().next() {
+            if let Some((open, close)) = syntax.block_comment.filter(|(o, _)| rest.starts_with(o)) {
+                let (comment, tail) = take_until(&rest[open.len()..], close);
+                push_comment(&mut doc.comments, comment);
+                push_gap(&mut doc.code, comment);
+                rest = tail;
+            } else if let Some(open) = syntax.line_comments.iter().find(|o| rest.starts_with(*o)) {
+                let (comment, tail) = take_until(&rest[open.len()..], "\n");
+                push_comment(&mut doc.comments, comment);
+                doc.code.push('\n');
+                rest = tail;
+            } else if let Some(len) = char_literal_len(rest).filter(|_| syntax.char_literals) {
+                doc.code.push_str(&rest[..len]);
+                rest = &rest[len..];
+            } else if let Some((string, tail)) =
+                syntax.take_literal(rest, doc.code.chars().next_back())
+            {
+                push_line(&mut doc.strings, string);
+                push_gap(&mut doc.code, string);
+                rest = tail;
+            } else {
+                doc.code.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+        doc
+    }
+}
+
+/// Splits the text at the first occurrence of the delimiter, dropping
+/// the delimiter. If the delimiter is not found, the whole text is taken.
+fn take_until<'a>(text: &'a str, delimiter: &str) -> (&'a str, &'a str) {
+    match text.find(delimiter) {
+        Some(end) => (&text[..end], &text[end + delimiter.len()..]),
+        None => (text, ""),
+    }
+}
+
+/// Returns the length of the character literal that the text starts with,
+/// if any, e.g. `'"'` or `'\''`. A quote that is not closed right after a
+/// character or an escape, e.g. of the lifetime `'a`, does not start one.
+fn char_literal_len(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix('\'')?;
+    let body_len = match rest.strip_prefix('\\') {
+        Some(escape) => {
+            let c = escape.chars().next()?;
+            let tail = &escape[c.len_utf8()..];
+            let end = tail.find(['\'', '\n'])?;
+            if end > 8 {
+                return None;
+            }
+            1 + c.len_utf8() + end
+        }
+        None => rest
+            .chars()
+            .next()
+            .filter(|&c| c != '\'' && c != '\n')?
+            .len_utf8(),
+    };
+    rest[body_len..].starts_with('\'').then_some(body_len + 2)
+}
+
+/// Splits the text at the closing quote of a string literal, skipping
+/// escaped quotes.
+fn take_string<'a>(text: &'a str, quote: &Quote) -> (&'a str, &'a str) {
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' && quote.escape == Escape::Backslash {
+            escaped = true;
+        } else if text[i..].starts_with(quote.close) {
+            let tail = &text[i + quote.close.len()..];
+            if quote.escape == Escape::Double && tail.starts_with(quote.close) {
+                escaped = true;
+            } else {
+                return (&text[..i], tail);
+            }
+        } else if c == '\n' && !quote.multiline {
+            return (&text[..i], &text[i..]);
+        }
+    }
+    (text, "")
+}
+
+/// Replaces a comment or a string literal in the code with a space and
+/// the line breaks in it, so that the lines of the code remain the lines
+/// of the source.
+fn push_gap(code: &mut String, removed: &str) {
+    code.push(' ');
+    code.extend(removed.matches('\n').map(|_| '\n'));
+}
+
+/// Appends the text of a comment, without the markers of documentation
+/// comments, e.g. `///`, `//!` and `/**`.
+fn push_comment(buffer: &mut String, comment: &str) {
+    push_line(buffer, comment.trim_start_matches(['/', '!', '*']));
+}
+
+/// Appends a line of text, if it is not blank.
+fn push_line(buffer: &mut String, line: &str) {
+    let line = line.trim();
+    if !line.is_empty() {
+        buffer.push_str(line);
+        buffer.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rust_source() {
+        let source = "/// Parses the config.\n\
+            fn parse_config(path: &str) -> Config {\n\
+            \x20   let name = \"config \\\"file\\\"\"; // the file name\n\
+            \x20   /* unreachable */ load(path, 'c')\n\
+            }\n";
+        let doc = CodeReader::new(CodeLanguage::Rust).parse(source);
+        assert_eq!(
+            doc.comments(),
+            "Parses the config.\nthe file name\nunreachable\n"
+        );
+        assert_eq!(doc.strings(), "config \\\"file\\\"\n");
+        assert!(doc.code().contains("fn parse_config(path: &str) -> Config"));
+        assert!(doc.code().contains("load(path, 'c')"));
+        assert!(!doc.code().contains("file name"));
+    }
+
+    #[test]
+    fn test_char_literals() {
+        let source = "fn quote(c: char) -> bool { c == '\"' || c == '\\'' }\n\
+            fn parse_config<'a>(path: &'a str) { let s = \"hello world\"; }\n";
+        let doc = CodeReader::new(CodeLanguage::Rust).parse(source);
+        assert_eq!(doc.strings(), "hello world\n");
+        assert!(doc.code().contains("c == '\"' || c == '\\''"));
+        assert!(doc.code().contains("fn parse_config<'a>(path: &'a str)"));
+        assert_eq!(char_literal_len("'\\u{1F600}'"), Some(11));
+        assert_eq!(char_literal_len("'static str"), None);
+    }
+
+    #[test]
+    fn test_python_source() {
+        let source = "def parse_config(path):\n\
+            \x20   \"\"\"Parses the config.\"\"\"\n\
+            \x20   return load(path, 'utf-8')  # decode\n";
+        let doc = CodeReader::new(CodeLanguage::Python).parse(source);
+        assert_eq!(doc.comments(), "decode\n");
+        assert_eq!(doc.strings(), "Parses the config.\nutf-8\n");
+        assert!(doc.code().contains("return load(path,  )"));
+    }
+
+    #[test]
+    fn test_multiline_strings() {
+        let source = "let usage = \"search\n  <query>\";\n\
+            let pattern = r#\"say \"hi\"\"#; /* a\n b */\n\
+            let path = br\"C:\\\";\n";
+        let doc = CodeReader::new(CodeLanguage::Rust).parse(source);
+        assert_eq!(doc.strings(), "search\n  <query>\nsay \"hi\"\nC:\\\n");
+        assert_eq!(doc.comments(), "a\n b\n");
+        assert_eq!(doc.code().lines().count(), source.lines().count());
+        assert!(doc.code().contains("let path =  ;"));
+
+        let source = "var path = @\"C:\\\"\"data\"\"\nfile\";\n";
+        let doc = CodeReader::new(CodeLanguage::CSharp).parse(source);
+        assert_eq!(doc.strings(), "C:\\\"\"data\"\"\nfile\n");
+        assert_eq!(doc.code(), "var path =  \n;\n");
+
+        let source = "query := `say \\`\nstop := \"say \\\"hi\\\"\"\n";
+        let doc = CodeReader::new(CodeLanguage::Go).parse(source);
+        assert_eq!(doc.strings(), "say \\\nsay \\\"hi\\\"\n");
+    }
+}
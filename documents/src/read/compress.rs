@@ -0,0 +1,135 @@
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+use crate::read::archive::MemberPath;
+use crate::read::encoding;
+
+/// A compression format of documents, recognised by the suffix of the
+/// file name, e.g. `notes.md.gz`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Bzip2,
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the compression format based on the extension of the file.
+    pub fn from_extension(ext: &OsStr) -> Option<Self> {
+        match ext.to_str()? {
+            "gz" | "gzip" => Some(Self::Gzip),
+            "bz2" => Some(Self::Bzip2),
+            "zst" | "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Returns the compression format based on the path of the file.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        Self::from_extension(path.as_ref().extension()?)
+    }
+
+    /// Wraps a reader of compressed data in a reader that decompresses it
+    /// as it is read.
+    pub fn decoder<'a>(&self, reader: impl BufRead + 'a) -> io::Result<Box<dyn Read + 'a>> {
+        let decoder: Box<dyn Read + 'a> = match self {
+            Self::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+            Self::Bzip2 => Box::new(bzip2::bufread::MultiBzDecoder::new(reader)),
+            Self::Zstd => Box::new(zstd::Decoder::with_buffer(reader)?),
+        };
+        Ok(decoder)
+    }
+}
+
+/// Returns the path of a file without its compression suffix, e.g.
+/// `notes.md` for `notes.md.gz`. Paths of uncompressed files are
+/// returned as they are.
+pub fn strip_compression(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    match Compression::from_path(path) {
+        Some(_) => path.with_extension(""),
+        None => path.to_path_buf(),
+    }
+}
+
+/// Checks if the file at the specified path is read as it is stored,
+/// i.e. it is neither compressed nor a member of an archive.
+pub fn is_plain(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    Compression::from_path(path).is_none() && MemberPath::parse(path).is_none()
+}
+
+/// Opens the file at the specified path for reading. Compressed files
+/// are decompressed as they are read, and members of archives, e.g.
+/// `bundle.zip!/docs/intro.md`, are read from their archive.
+pub fn open(path: impl AsRef<Path>) -> io::Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let reader: Box<dyn BufRead> = match MemberPath::parse(path) {
+        Some(member_path) => Box::new(Cursor::new(member_path.read()?)),
+        None => Box::new(BufReader::new(File::open(path)?)),
+    };
+    match Compression::from_path(path) {
+        Some(compression) => Ok(Box::new(BufReader::new(compression.decoder(reader)?))),
+        None => Ok(reader),
+    }
+}
+
+/// Reads the contents of the file at the specified path into a string,
+/// decompressing them if the file is compressed. The contents are
+/// transcoded to UTF-8 from the encoding that they are detected to have.
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    Ok(encoding::decode(&read(path)?)?.0)
+}
+
+/// Reads the contents of the file at the specified path into a vector of
+/// bytes, decompressing them if the file is compressed.
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    open(path)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_strip_compression() {
+        assert_eq!(strip_compression("notes.md.gz"), Path::new("notes.md"));
+        assert_eq!(strip_compression("dump.xml.zst"), Path::new("dump.xml"));
+        assert_eq!(strip_compression("notes.md"), Path::new("notes.md"));
+    }
+
+    #[test]
+    fn test_decoders() {
+        let text = "Compressed notes.\n";
+
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(text.as_bytes()).unwrap();
+        let gzip = gzip.finish().unwrap();
+
+        let mut bzip2 = bzip2::write::BzEncoder::new(Vec::new(), bzip2::Compression::default());
+        bzip2.write_all(text.as_bytes()).unwrap();
+        let bzip2 = bzip2.finish().unwrap();
+
+        let zstd = zstd::encode_all(text.as_bytes(), 0).unwrap();
+
+        for (compression, data) in [
+            (Compression::Gzip, gzip),
+            (Compression::Bzip2, bzip2),
+            (Compression::Zstd, zstd),
+        ] {
+            let mut decompressed = String::new();
+            compression
+                .decoder(data.as_slice())
+                .unwrap()
+                .read_to_string(&mut decompressed)
+                .unwrap();
+            assert_eq!(decompressed, text, "{compression:?}");
+        }
+    }
+}
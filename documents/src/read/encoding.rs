@@ -0,0 +1,225 @@
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::read::compress;
+
+/// The characters of Windows-1252 from `0x80` to `0x9F`, where it differs
+/// from Latin-1. The bytes that Windows-1252 does not define are mapped to
+/// the control characters of Latin-1.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+/// The byte order marks of the encodings, which are removed from text.
+const BYTE_ORDER_MARKS: [(&[u8], Encoding); 3] = [
+    (b"\xEF\xBB\xBF", Encoding::Utf8),
+    (b"\xFF\xFE", Encoding::Utf16Le),
+    (b"\xFE\xFF", Encoding::Utf16Be),
+];
+
+/// The share of control characters above which a file is taken to be
+/// binary rather than text.
+const MAX_CONTROL_RATIO: f64 = 0.1;
+
+/// The character encoding of a text file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+    Latin1,
+}
+
+impl Encoding {
+    /// Returns the name of the encoding, as it is registered with IANA.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Windows1252 => "windows-1252",
+            Self::Latin1 => "ISO-8859-1",
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Detects the encoding of the bytes of a text file, and decodes them
+/// into a string.
+///
+/// Byte order marks are recognised and removed. Without one, text is
+/// UTF-8 if it is valid UTF-8, UTF-16 if every other byte is zero, and
+/// otherwise Windows-1252, or Latin-1 if it has no byte that the two
+/// encodings disagree on.
+///
+/// # Errors
+///
+/// Returns an error of kind `InvalidData` if the bytes are binary data,
+/// e.g. they have zero bytes or many control characters.
+///
+/// # Examples
+///
+/// ```
+/// use documents::read::encoding::{decode, Encoding};
+///
+/// let (text, encoding) = decode(b"caf\xe9").unwrap();
+/// assert_eq!(text, "café");
+/// assert_eq!(encoding, Encoding::Latin1);
+/// assert!(decode(b"\x7fELF\x02\x01\x01\x00").is_err());
+/// ```
+pub fn decode(bytes: &[u8]) -> io::Result<(String, Encoding)> {
+    for (bom, encoding) in BYTE_ORDER_MARKS {
+        if let Some(bytes) = bytes.strip_prefix(bom) {
+            let text = match encoding {
+                Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+                _ => decode_utf16(bytes, encoding),
+            };
+            return Ok((text, encoding));
+        }
+    }
+    if bytes.contains(&0) {
+        let encoding = detect_utf16(bytes).ok_or_else(binary_error)?;
+        let text = decode_utf16(bytes, encoding);
+        if has_many_controls(text.chars()) {
+            return Err(binary_error());
+        }
+        return Ok((text, encoding));
+    }
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Ok((text.to_string(), Encoding::Utf8));
+    }
+
+    if has_many_controls(bytes.iter().map(|&byte| char::from(byte))) {
+        return Err(binary_error());
+    }
+    if bytes.iter().any(|byte| (0x80..0xA0).contains(byte)) {
+        let text = bytes
+            .iter()
+            .map(|&byte| match byte {
+                0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                _ => char::from(byte),
+            })
+            .collect();
+        Ok((text, Encoding::Windows1252))
+    } else {
+        let text = bytes.iter().map(|&byte| char::from(byte)).collect();
+        Ok((text, Encoding::Latin1))
+    }
+}
+
+/// Checks if the bytes start with a byte order mark.
+pub fn has_bom(bytes: &[u8]) -> bool {
+    BYTE_ORDER_MARKS
+        .iter()
+        .any(|(bom, _)| bytes.starts_with(bom))
+}
+
+/// Reads the text file at the specified path, detecting its encoding.
+/// Compressed files and members of archives are read too.
+pub fn read_text(path: impl AsRef<Path>) -> io::Result<(String, Encoding)> {
+    decode(&compress::read(path)?)
+}
+
+/// Detects UTF-16 text without a byte order mark, from the zero bytes
+/// that precede or follow the ASCII characters.
+fn detect_utf16(bytes: &[u8]) -> Option<Encoding> {
+    if !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let pairs = bytes.len() / 2;
+    let zeros_at = |offset: usize| {
+        bytes
+            .iter()
+            .skip(offset)
+            .step_by(2)
+            .filter(|&&b| b == 0)
+            .count()
+    };
+    let (even, odd) = (zeros_at(0), zeros_at(1));
+    // Text in other scripts has fewer zero bytes, so half of the pairs suffice.
+    match (even, odd) {
+        (0, odd) if odd * 2 >= pairs => Some(Encoding::Utf16Le),
+        (even, 0) if even * 2 >= pairs => Some(Encoding::Utf16Be),
+        _ => None,
+    }
+}
+
+/// Decodes UTF-16 text, replacing unpaired surrogates.
+fn decode_utf16(bytes: &[u8], encoding: Encoding) -> String {
+    let units = bytes.chunks_exact(2).map(|pair| match encoding {
+        Encoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+        _ => u16::from_le_bytes([pair[0], pair[1]]),
+    });
+    char::decode_utf16(units)
+        .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        .collect()
+}
+
+/// Checks if more of the characters than the share that text may have are
+/// control characters, other than whitespace and escapes.
+fn has_many_controls(chars: impl Iterator<Item = char>) -> bool {
+    let (mut count, mut controls) = (0, 0);
+    for c in chars {
+        count += 1;
+        if c < '\u{20}' && !"\t\n\r\x0C\x1B".contains(c) {
+            controls += 1;
+        }
+    }
+    controls as f64 > MAX_CONTROL_RATIO * count as f64
+}
+
+fn binary_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "file is binary, not text")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_unicode() {
+        assert_eq!(
+            decode("naïve".as_bytes()).unwrap(),
+            ("naïve".to_string(), Encoding::Utf8)
+        );
+        assert_eq!(
+            decode(b"\xEF\xBB\xBFbom").unwrap(),
+            ("bom".to_string(), Encoding::Utf8)
+        );
+        assert_eq!(
+            decode(b"\xFF\xFEh\x00i\x00").unwrap(),
+            ("hi".to_string(), Encoding::Utf16Le)
+        );
+        assert_eq!(
+            decode(b"\x00h\x00i\x00!").unwrap(),
+            ("hi!".to_string(), Encoding::Utf16Be)
+        );
+    }
+
+    #[test]
+    fn test_decode_legacy() {
+        assert_eq!(
+            decode(b"\x93quoted\x94 \x80 5").unwrap(),
+            ("“quoted” € 5".to_string(), Encoding::Windows1252)
+        );
+        assert_eq!(
+            decode(b"Stra\xdfe").unwrap(),
+            ("Straße".to_string(), Encoding::Latin1)
+        );
+    }
+
+    #[test]
+    fn test_decode_binary() {
+        let error = decode(b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(decode(b"\x01\x02\x03\xff\x04\x05").is_err());
+    }
+}
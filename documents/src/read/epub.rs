@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+use xml::reader::XmlEvent;
+use xml::EventReader;
+use zip::ZipArchive;
+
+use crate::core::metadata::Section;
+use crate::read::compress;
+use crate::read::read_zip_entry;
+use crate::read::xml::extract_text;
+
+/// The text and metadata extracted from an EPUB e-book.
+#[derive(Debug, Default)]
+pub struct EpubDocument {
+    text: String,
+    title: Option<String>,
+    author: Option<String>,
+    chapters: Vec<Section>,
+}
+
+impl EpubDocument {
+    /// Returns the text of the book, with its chapters in reading order.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the title of the book, if it is known.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the author of the book, if it is known.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Returns the chapters of the book, with their byte offsets in
+    /// the text.
+    pub fn chapters(&self) -> &[Section] {
+        &self.chapters
+    }
+
+    /// Consumes the document and returns its text.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// The package document (OPF) of an EPUB, which lists the metadata
+/// of the book and the order of its chapters.
+#[derive(Debug, Default)]
+struct Package {
+    title: Option<String>,
+    author: Option<String>,
+    manifest: HashMap<String, String>,
+    spine: Vec<String>,
+}
+
+/// A reader for EPUB e-books.
+///
+/// The chapters of the book are read in the order of the spine of
+/// its package document, and their text is extracted as XML.
+#[derive(Debug, Default, Clone)]
+pub struct EpubReader;
+
+impl EpubReader {
+    /// Creates a new EPUB reader.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reads the EPUB file at the specified path.
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<EpubDocument> {
+        let path = path.as_ref();
+        if compress::is_plain(path) {
+            self.read_from(BufReader::new(File::open(path)?))
+        } else {
+            // Zip archives are read by seeking, so they are read into memory.
+            self.read_from(Cursor::new(compress::read(path)?))
+        }
+    }
+
+    /// Reads an EPUB from a reader.
+    pub fn read_from(&self, reader: impl Read + Seek) -> io::Result<EpubDocument> {
+        let mut archive = ZipArchive::new(reader)?;
+        let container = read_zip_entry(&mut archive, "META-INF/container.xml")?;
+        let package_path = find_package_path(&container)
+            .ok_or_else(|| invalid_data("EPUB container does not list a package document"))?;
+        let package = parse_package(&read_zip_entry(&mut archive, &package_path)?);
+        let base_dir = match package_path.rfind('/') {
+            Some(idx) => &package_path[..=idx],
+            None => "",
+        };
+
+        let mut doc = EpubDocument {
+            title: package.title,
+            author: package.author,
+            ..Default::default()
+        };
+        for idref in &package.spine {
+            let Some(href) = package.manifest.get(idref) else {
+                continue;
+            };
+            let href = href.split('#').next().unwrap_or(href);
+            let chapter = extract_text(
+                read_zip_entry(&mut archive, &format!("{base_dir}{href}"))?.as_bytes(),
+            );
+            if chapter.text().trim().is_empty() {
+                continue;
+            }
+            let heading = chapter
+                .heading()
+                .or(chapter.title())
+                .filter(|heading| !heading.is_empty())
+                .unwrap_or(href);
+            doc.chapters
+                .push(Section::new(heading.to_string(), 1, doc.text.len()));
+            doc.text.push_str(chapter.text());
+            doc.text.push('\n');
+        }
+        Ok(doc)
+    }
+}
+
+/// Finds the path of the package document in the EPUB container file.
+fn find_package_path(container: &str) -> Option<String> {
+    EventReader::new(container.as_bytes())
+        .into_iter()
+        .flatten()
+        .find_map(|event| match event {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } if name.local_name == "rootfile" => attributes
+                .into_iter()
+                .find(|attr| attr.name.local_name == "full-path")
+                .map(|attr| attr.value),
+            _ => None,
+        })
+}
+
+/// Parses the package document of an EPUB.
+fn parse_package(opf: &str) -> Package {
+    let mut package = Package::default();
+    let mut element = String::new();
+    for event in EventReader::new(opf.as_bytes()).into_iter().flatten() {
+        match event {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let attr = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|attr| attr.name.local_name == key)
+                        .map(|attr| attr.value.clone())
+                };
+                match name.local_name.as_str() {
+                    "item" => {
+                        if let (Some(id), Some(href)) = (attr("id"), attr("href")) {
+                            package.manifest.insert(id, href);
+                        }
+                    }
+                    "itemref" => package.spine.extend(attr("idref")),
+                    _ => {}
+                }
+                element = name.local_name;
+            }
+            XmlEvent::Characters(text) => match element.as_str() {
+                "title" if package.title.is_none() => package.title = Some(text.trim().to_string()),
+                "creator" if package.author.is_none() => {
+                    package.author = Some(text.trim().to_string())
+                }
+                _ => {}
+            },
+            XmlEvent::EndElement { .. } => element.clear(),
+            _ => {}
+        }
+    }
+    package
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::*;
+
+    const CONTAINER: &str = r#"<?xml version="1.0"?>
+        <container xmlns="urn:oasis:names:tc:opendocument:xmlns:container" version="1.0">
+          <rootfiles>
+            <rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/>
+          </rootfiles>
+        </container>"#;
+
+    const PACKAGE: &str = r#"<?xml version="1.0"?>
+        <package xmlns="http://www.idpf.org/2007/opf" version="3.0">
+          <metadata xmlns:dc="http://purl.org/dc/elements/1.1/">
+            <dc:title>The Rust Book</dc:title>
+            <dc:creator>Ferris</dc:creator>
+          </metadata>
+          <manifest>
+            <item id="ch2" href="text/ch2.xhtml" media-type="application/xhtml+xml"/>
+            <item id="ch1" href="text/ch1.xhtml" media-type="application/xhtml+xml"/>
+          </manifest>
+          <spine>
+            <itemref idref="ch1"/>
+            <itemref idref="ch2"/>
+          </spine>
+        </package>"#;
+
+    fn build_epub() -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let files = [
+            ("mimetype", "application/epub+zip"),
+            ("META-INF/container.xml", CONTAINER),
+            ("OEBPS/content.opf", PACKAGE),
+            (
+                "OEBPS/text/ch1.xhtml",
+                "<html><body><h1>Ownership</h1><p>Each value has an owner.</p></body></html>",
+            ),
+            (
+                "OEBPS/text/ch2.xhtml",
+                "<html><body><h1>Borrowing</h1><p>References borrow values.</p></body></html>",
+            ),
+        ];
+        for (name, contents) in files {
+            writer
+                .start_file(name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        Cursor::new(writer.finish().unwrap().into_inner())
+    }
+
+    #[test]
+    fn test_read_epub() {
+        let doc = EpubReader::new().read_from(build_epub()).unwrap();
+        assert_eq!(doc.title(), Some("The Rust Book"));
+        assert_eq!(doc.author(), Some("Ferris"));
+
+        let chapters = doc.chapters();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].heading(), "Ownership");
+        assert_eq!(chapters[1].heading(), "Borrowing");
+        assert!(doc.text()[chapters[1].offset()..].contains("References borrow values."));
+        assert!(!doc.text()[..chapters[1].offset()].contains("References"));
+    }
+}
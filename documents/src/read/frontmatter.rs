@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use crate::core::field::{Date, FieldValue};
+
+/// The front matter of a document, i.e. a block of YAML (delimited by
+/// `---`) or TOML (delimited by `+++`) at the start of the document.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FrontMatter {
+    fields: BTreeMap<String, FieldValue>,
+}
+
+impl FrontMatter {
+    /// Splits a text into its front matter and its body.
+    ///
+    /// If the text does not start with a front matter block, or the
+    /// block cannot be parsed, `None` is returned along with the whole
+    /// text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use documents::read::frontmatter::FrontMatter;
+    ///
+    /// let text = "---\ntitle: Notes\n---\nBody";
+    /// let (front_matter, body) = FrontMatter::split(text);
+    /// assert_eq!(front_matter.unwrap().fields().len(), 1);
+    /// assert_eq!(body, "Body");
+    /// ```
+    pub fn split(text: &str) -> (Option<Self>, &str) {
+        let stripped = text.strip_prefix('\u{feff}').unwrap_or(text);
+        let Some((first_line, rest)) = stripped.split_once('\n') else {
+            return (None, text);
+        };
+        let (format, closing) = match first_line.trim_end() {
+            "---" => (Format::Yaml, &["---", "..."][..]),
+            "+++" => (Format::Toml, &["+++"][..]),
+            _ => return (None, text),
+        };
+
+        let mut offset = 0;
+        for line in rest.split_inclusive('\n') {
+            if closing.contains(&line.trim_end()) {
+                let body = &rest[offset + line.len()..];
+                let block = &rest[..offset];
+                let front_matter = match format {
+                    Format::Yaml => Self::from_yaml(block),
+                    Format::Toml => Self::from_toml(block),
+                };
+                return match front_matter {
+                    Some(front_matter) => (Some(front_matter), body),
+                    None => (None, text),
+                };
+            }
+            offset += line.len();
+        }
+        (None, text)
+    }
+
+    /// Parses a YAML front matter block. Only mappings are accepted.
+    fn from_yaml(block: &str) -> Option<Self> {
+        let value = serde_yaml::from_str::<serde_yaml::Value>(block).ok()?;
+        let fields = match value {
+            serde_yaml::Value::Mapping(mapping) => mapping
+                .into_iter()
+                .filter_map(|(key, value)| Some((key.as_str()?.to_string(), yaml_field(value)?)))
+                .collect(),
+            serde_yaml::Value::Null => BTreeMap::new(),
+            _ => return None,
+        };
+        Some(Self::from_fields(fields))
+    }
+
+    /// Parses a TOML front matter block.
+    fn from_toml(block: &str) -> Option<Self> {
+        let table = block.parse::<toml::Table>().ok()?;
+        let fields = table
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, toml_field(value)?)))
+            .collect();
+        Some(Self::from_fields(fields))
+    }
+
+    /// Creates a front matter from parsed fields, lowercasing the names.
+    fn from_fields(fields: BTreeMap<String, FieldValue>) -> Self {
+        let fields = fields
+            .into_iter()
+            .map(|(name, value)| (name.to_lowercase(), value))
+            .collect();
+        Self { fields }
+    }
+
+    /// Returns the fields of the front matter.
+    pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+        &self.fields
+    }
+
+    /// Consumes the front matter and returns its fields.
+    pub fn into_fields(self) -> BTreeMap<String, FieldValue> {
+        self.fields
+    }
+}
+
+/// The format of a front matter block.
+enum Format {
+    Yaml,
+    Toml,
+}
+
+/// Converts a text value to a field, recognising dates.
+pub(crate) fn text_field(text: String) -> FieldValue {
+    match text.parse::<Date>() {
+        Ok(date) => FieldValue::Date(date),
+        Err(_) => FieldValue::Text(text),
+    }
+}
+
+/// Converts a YAML value to a field. Nested mappings are not supported.
+fn yaml_field(value: serde_yaml::Value) -> Option<FieldValue> {
+    use serde_yaml::Value;
+
+    match value {
+        Value::String(text) => Some(text_field(text)),
+        Value::Number(number) => Some(FieldValue::Number(number.as_f64()?)),
+        Value::Bool(bool) => Some(FieldValue::Bool(bool)),
+        Value::Sequence(items) => {
+            let items = items
+                .into_iter()
+                .filter_map(|item| match yaml_field(item)? {
+                    FieldValue::List(_) => None,
+                    item => Some(item.to_string()),
+                })
+                .collect();
+            Some(FieldValue::List(items))
+        }
+        Value::Tagged(tagged) => yaml_field(tagged.value),
+        Value::Null | Value::Mapping(_) => None,
+    }
+}
+
+/// Converts a TOML value to a field. Nested tables are not supported.
+fn toml_field(value: toml::Value) -> Option<FieldValue> {
+    use toml::Value;
+
+    match value {
+        Value::String(text) => Some(text_field(text)),
+        Value::Integer(number) => Some(FieldValue::Number(number as f64)),
+        Value::Float(number) => Some(FieldValue::Number(number)),
+        Value::Boolean(bool) => Some(FieldValue::Bool(bool)),
+        Value::Datetime(datetime) => Some(text_field(datetime.to_string())),
+        Value::Array(items) => {
+            let items = items
+                .into_iter()
+                .filter_map(|item| match toml_field(item)? {
+                    FieldValue::List(_) => None,
+                    item => Some(item.to_string()),
+                })
+                .collect();
+            Some(FieldValue::List(items))
+        }
+        Value::Table(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_front_matter() {
+        let text = "---\n\
+            Title: Release notes\n\
+            tags: [rust, search]\n\
+            date: 2024-01-15\n\
+            draft: false\n\
+            ---\n\
+            # Notes\n";
+        let (front_matter, body) = FrontMatter::split(text);
+        let fields = front_matter.unwrap().into_fields();
+        assert_eq!(body, "# Notes\n");
+        assert_eq!(
+            fields["title"],
+            FieldValue::Text("Release notes".to_string())
+        );
+        assert_eq!(
+            fields["tags"],
+            FieldValue::List(vec!["rust".to_string(), "search".to_string()])
+        );
+        assert_eq!(
+            fields["date"],
+            FieldValue::Date(Date::new(2024, 1, 15).unwrap())
+        );
+        assert_eq!(fields["draft"], FieldValue::Bool(false));
+    }
+
+    #[test]
+    fn test_toml_front_matter() {
+        let text = "+++\nauthor = \"Ada\"\ndate = 2023-05-01\n+++\nBody\n";
+        let (front_matter, body) = FrontMatter::split(text);
+        let fields = front_matter.unwrap().into_fields();
+        assert_eq!(body, "Body\n");
+        assert_eq!(fields["author"], FieldValue::Text("Ada".to_string()));
+        assert_eq!(
+            fields["date"],
+            FieldValue::Date(Date::new(2023, 5, 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_no_front_matter() {
+        let text = "# Title\n---\nnot: front matter\n---\n";
+        assert_eq!(FrontMatter::split(text), (None, text));
+        let unclosed = "---\ntitle: x\n";
+        assert_eq!(FrontMatter::split(unclosed), (None, unclosed));
+    }
+}
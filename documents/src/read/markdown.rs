@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use crate::core::field::FieldValue;
+use crate::core::metadata::Section;
+use crate::read::compress;
+use crate::read::frontmatter::FrontMatter;
+
+/// The text and structure extracted from a Markdown file.
+#[derive(Debug, Default)]
+pub struct MarkdownDocument {
+    text: String,
+    title: Option<String>,
+    sections: Vec<Section>,
+    fields: BTreeMap<String, FieldValue>,
+}
+
+impl MarkdownDocument {
+    /// Returns the extracted plain text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the title set in the front matter, or else the text of
+    /// the first heading, if there is one.
+    pub fn title(&self) -> Option<&str> {
+        self.fields
+            .get("title")
+            .and_then(FieldValue::as_text)
+            .or(self.title.as_deref())
+    }
+
+    /// Returns the section headings, with their byte offsets in the
+    /// extracted text.
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Returns the fields of the front matter. If the document has no
+    /// front matter, the map is empty.
+    pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+        &self.fields
+    }
+
+    /// Consumes the document and returns its text, title and sections.
+    pub fn into_parts(self) -> (String, Option<String>, Vec<Section>) {
+        let title = self.title().map(str::to_string);
+        (self.text, title, self.sections)
+    }
+
+    /// Consumes the document and returns its text.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// A reader that parses Markdown and extracts its plain text.
+///
+/// Markup such as emphasis markers, link URLs and table pipes is
+/// dropped, and only the text a reader would see is kept. Fenced and
+/// indented code blocks are kept unless the reader is configured to
+/// skip them. A YAML or TOML front matter block is parsed into fields,
+/// and is not part of the text.
+#[derive(Debug, Clone)]
+pub struct MarkdownReader {
+    code_blocks: bool,
+}
+
+impl Default for MarkdownReader {
+    fn default() -> Self {
+        Self { code_blocks: true }
+    }
+}
+
+impl MarkdownReader {
+    /// Creates a new Markdown reader that keeps code blocks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether the contents of code blocks are kept.
+    pub fn with_code_blocks(self, code_blocks: bool) -> Self {
+        Self { code_blocks }
+    }
+
+    /// Reads and parses the Markdown file at the specified path.
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<MarkdownDocument> {
+        let markdown = compress::read_to_string(path)?;
+        Ok(self.parse(&markdown))
+    }
+
+    /// Parses a Markdown string.
+    pub fn parse(&self, markdown: &str) -> MarkdownDocument {
+        let options = Options::ENABLE_TABLES
+            | Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_FOOTNOTES;
+        let (front_matter, markdown) = FrontMatter::split(markdown);
+        let mut doc = MarkdownDocument {
+            fields: front_matter
+                .map(FrontMatter::into_fields)
+                .unwrap_or_default(),
+            ..Default::default()
+        };
+        let mut heading: Option<(HeadingLevel, usize)> = None;
+        let mut in_code_block = false;
+
+        for event in Parser::new_ext(markdown, options) {
+            match event {
+                Event::Start(Tag::Heading { level, .. }) => {
+                    heading = Some((level, doc.text.len()));
+                }
+                Event::End(TagEnd::Heading(_)) => {
+                    if let Some((level, offset)) = heading.take() {
+                        let text = doc.text[offset..].trim().to_string();
+                        if doc.title.is_none() {
+                            doc.title = Some(text.clone());
+                        }
+                        doc.sections.push(Section::new(text, level as u8, offset));
+                    }
+                    doc.text.push('\n');
+                }
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => {
+                    in_code_block = false;
+                    push_break(&mut doc.text);
+                }
+                Event::Text(text) if !in_code_block || self.code_blocks => {
+                    doc.text.push_str(&text);
+                }
+                Event::Code(code) => doc.text.push_str(&code),
+                Event::SoftBreak | Event::HardBreak => doc.text.push('\n'),
+                Event::End(TagEnd::TableCell) => doc.text.push(' '),
+                Event::End(
+                    TagEnd::Paragraph
+                    | TagEnd::Item
+                    | TagEnd::TableRow
+                    | TagEnd::TableHead
+                    | TagEnd::BlockQuote(_),
+                ) => push_break(&mut doc.text),
+                _ => {}
+            }
+        }
+        doc
+    }
+}
+
+/// Ends the current line of text, unless it is already ended.
+fn push_break(text: &mut String) {
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKDOWN: &str = "# Guide\n\
+        \n\
+        Read the [manual](https://example.com/manual) *carefully*.\n\
+        \n\
+        ## Install\n\
+        \n\
+        | Tool | Version |\n\
+        |------|---------|\n\
+        | rust | 1.80    |\n\
+        \n\
+        ```sh\n\
+        cargo install searchine\n\
+        ```\n";
+
+    #[test]
+    fn test_markdown_text_and_sections() {
+        let doc = MarkdownReader::new().parse(MARKDOWN);
+        assert_eq!(doc.title(), Some("Guide"));
+        assert!(doc.text().contains("Read the manual carefully."));
+        assert!(!doc.text().contains("https"));
+        assert!(!doc.text().contains('|'));
+        assert!(doc.text().contains("cargo install searchine"));
+
+        let sections = doc.sections();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[1].heading(), "Install");
+        assert_eq!(sections[1].level(), 2);
+        assert!(doc.text()[sections[1].offset()..].starts_with("Install"));
+    }
+
+    #[test]
+    fn test_markdown_without_code_blocks() {
+        let doc = MarkdownReader::new().with_code_blocks(false).parse(MARKDOWN);
+        assert!(!doc.text().contains("cargo install"));
+        assert!(doc.text().contains("rust 1.80"));
+    }
+
+    #[test]
+    fn test_markdown_with_front_matter() {
+        let markdown = format!("---\ntitle: Setup\ntags: [rust]\n---\n{MARKDOWN}");
+        let doc = MarkdownReader::new().parse(&markdown);
+        assert_eq!(doc.title(), Some("Setup"));
+        assert!(doc.fields().contains_key("tags"));
+        assert!(doc.text().starts_with("Guide"));
+        let (_, title, _) = doc.into_parts();
+        assert_eq!(title.as_deref(), Some("Setup"));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+use std::io::{self, Read};
+
+use xml::reader::{Events, XmlEvent};
+use xml::EventReader;
+
+use crate::core::field::{Date, FieldValue};
+use crate::read::records::Record;
+
+/// A page of a MediaWiki dump, while it is being read.
+#[derive(Debug, Default)]
+struct Page {
+    id: Option<String>,
+    title: Option<String>,
+    text: String,
+    timestamp: Option<Date>,
+    redirect: bool,
+}
+
+impl Page {
+    /// Converts the page into a record, whose key is the ID of the page.
+    /// Redirects and pages without an ID have no record.
+    fn into_record(self) -> Option<Record> {
+        if self.redirect {
+            return None;
+        }
+        let mut fields = BTreeMap::new();
+        if let Some(timestamp) = self.timestamp {
+            fields.insert("modified".to_string(), FieldValue::Date(timestamp));
+        }
+        let text = match self.title {
+            Some(title) => {
+                let text = format!("{title}\n{}", self.text);
+                fields.insert("title".to_string(), FieldValue::Text(title));
+                text
+            }
+            None => self.text,
+        };
+        Some(Record::new(self.id?, text, fields))
+    }
+}
+
+/// A streaming reader of the pages of a MediaWiki XML dump.
+///
+/// Each `<page>` element of the dump is read as a record, whose key is
+/// the ID of the page. The text of the record is the title of the page
+/// and the wikitext of its latest revision in the dump. Redirect pages
+/// are skipped.
+pub struct MediaWikiRecords<R: Read> {
+    events: Events<R>,
+    done: bool,
+}
+
+impl<R: Read> MediaWikiRecords<R> {
+    /// Creates a new reader of the pages of a MediaWiki dump.
+    pub fn new(reader: R) -> Self {
+        Self {
+            events: EventReader::new(reader).into_iter(),
+            done: false,
+        }
+    }
+
+    /// Reads the events of the dump until the end of the next page.
+    fn next_page(&mut self) -> io::Result<Option<Page>> {
+        let mut page: Option<Page> = None;
+        // The path of the elements from the `<page>` element to the current element.
+        let mut path: Vec<String> = Vec::new();
+        for event in self.events.by_ref() {
+            let event = event.map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            match event {
+                XmlEvent::StartElement { name, .. } if name.local_name == "page" => {
+                    page = Some(Page::default());
+                    path.clear();
+                }
+                XmlEvent::StartElement { name, .. } if page.is_some() => {
+                    let page = page.as_mut().unwrap();
+                    match name.local_name.as_str() {
+                        "redirect" if path.is_empty() => page.redirect = true,
+                        // Full-history dumps have many revisions per page,
+                        // of which only the last one is kept.
+                        "revision" if path.is_empty() => {
+                            page.text.clear();
+                            page.timestamp = None;
+                        }
+                        _ => {}
+                    }
+                    path.push(name.local_name);
+                }
+                XmlEvent::EndElement { name } if name.local_name == "page" && page.is_some() => {
+                    return Ok(page);
+                }
+                XmlEvent::EndElement { .. } => {
+                    path.pop();
+                }
+                XmlEvent::Characters(chars) | XmlEvent::CData(chars) => {
+                    let Some(page) = page.as_mut() else {
+                        continue;
+                    };
+                    let path = path.iter().map(String::as_str).collect::<Vec<_>>();
+                    match path.as_slice() {
+                        ["id"] => page.id = Some(chars.trim().to_string()),
+                        ["title"] => page.title = Some(chars.trim().to_string()),
+                        ["revision", "text"] => page.text.push_str(&chars),
+                        ["revision", "timestamp"] => page.timestamp = chars.trim().parse().ok(),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<R: Read> Iterator for MediaWikiRecords<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            match self.next_page() {
+                Ok(Some(page)) => {
+                    if let Some(record) = page.into_record() {
+                        return Some(Ok(record));
+                    }
+                }
+                Ok(None) => self.done = true,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mediawiki_records() {
+        let dump = r#"<mediawiki xmlns="http://www.mediawiki.org/xml/export-0.10/">
+              <siteinfo><sitename>Wikipedia</sitename></siteinfo>
+              <page>
+                <title>Rust (programming language)</title>
+                <ns>0</ns>
+                <id>29414838</id>
+                <revision>
+                  <id>1234</id>
+                  <timestamp>2024-06-01T12:00:00Z</timestamp>
+                  <text xml:space="preserve">'''Rust''' is a [[programming language]].</text>
+                </revision>
+              </page>
+              <page>
+                <title>Rust language</title>
+                <id>5</id>
+                <redirect title="Rust (programming language)" />
+                <revision><id>6</id><text>#REDIRECT [[Rust (programming language)]]</text></revision>
+              </page>
+            </mediawiki>"#;
+        let records = MediaWikiRecords::new(dump.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].key(), "29414838");
+        assert!(records[0]
+            .text()
+            .starts_with("Rust (programming language)\n"));
+        assert!(records[0].text().contains("is a [[programming language]]"));
+        assert_eq!(
+            records[0].fields()["modified"],
+            FieldValue::Date(Date::new(2024, 6, 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_mediawiki_revisions() {
+        let dump = r#"<mediawiki>
+              <page>
+                <title>A</title>
+                <id>1</id>
+                <revision>
+                  <timestamp>2023-01-01T00:00:00Z</timestamp>
+                  <text>old revision text</text>
+                </revision>
+                <revision>
+                  <timestamp>2024-01-01T00:00:00Z</timestamp>
+                  <text>new revision text</text>
+                </revision>
+              </page>
+            </mediawiki>"#;
+        let records = MediaWikiRecords::new(dump.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records[0].text(), "A\nnew revision text");
+        assert_eq!(
+            records[0].fields()["modified"],
+            FieldValue::Date(Date::new(2024, 1, 1).unwrap())
+        );
+    }
+}
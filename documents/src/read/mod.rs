@@ -0,0 +1,179 @@
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::core::dtype::DocumentType;
+pub use crate::read::code::{CodeDocument, CodeReader};
+pub use crate::read::compress::Compression;
+pub use crate::read::encoding::Encoding;
+pub use crate::read::epub::{EpubDocument, EpubReader};
+pub use crate::read::markdown::{MarkdownDocument, MarkdownReader};
+pub use crate::read::office::{OfficeDocument, OfficeFormat, OfficeReader};
+pub use crate::read::records::{Record, RecordFormat, RecordReader, Records};
+
+pub mod archive;
+pub mod code;
+pub mod compress;
+pub mod encoding;
+pub mod epub;
+pub mod frontmatter;
+pub mod markdown;
+pub mod mediawiki;
+pub mod office;
+pub mod records;
+pub mod trec;
+pub mod xml;
+
+/// Options that control how the contents of documents are read.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadOptions {
+    /// Whether the contents of code blocks in Markdown documents
+    /// are read.
+    pub code_blocks: bool,
+    /// Whether the comments and string literals of source files are
+    /// read into separate text fields, instead of the page content.
+    pub code_fields: bool,
+    /// Whether JSON files hold records, i.e. whether each item of a JSON
+    /// file whose top-level value is an array is read as a record. If not,
+    /// JSON files, e.g. `package.json`, are read as plain text.
+    pub json_records: bool,
+    /// The name of the field with the ID of each record of JSONL, CSV
+    /// and JSON files.
+    pub record_id_field: String,
+    /// The names of the fields that make up the text of each record.
+    /// If empty, all the text fields of the record are read.
+    pub record_text_fields: Vec<String>,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            code_blocks: true,
+            code_fields: false,
+            json_records: false,
+            record_id_field: "id".to_string(),
+            record_text_fields: Vec::new(),
+        }
+    }
+}
+
+impl ReadOptions {
+    /// Returns a Markdown reader configured with the options.
+    pub fn markdown_reader(&self) -> MarkdownReader {
+        MarkdownReader::new().with_code_blocks(self.code_blocks)
+    }
+
+    /// Returns a reader configured with the options for documents of
+    /// the specified type, if it is a type of file that holds many
+    /// records. JSON files only hold records if `json_records` is set.
+    pub fn record_reader(&self, document_type: DocumentType) -> Option<RecordReader> {
+        let format = match document_type {
+            DocumentType::Jsonl => RecordFormat::Jsonl,
+            DocumentType::Csv => RecordFormat::Csv,
+            DocumentType::Json if self.json_records => RecordFormat::Json,
+            DocumentType::Trec => RecordFormat::Trec,
+            DocumentType::MediaWiki => RecordFormat::MediaWiki,
+            _ => return None,
+        };
+        let reader = RecordReader::new(format)
+            .with_id_field(&self.record_id_field)
+            .with_text_fields(&self.record_text_fields);
+        Some(reader)
+    }
+}
+
+/// Reads the contents of a file and returns it as a string.
+///
+/// This function determines the type of the file based on its path and reads its
+/// contents accordingly. If the file type is unsupported, it returns an error.
+///
+/// # Arguments
+///
+/// * `path` - A path to the file to be read.
+///
+/// # Returns
+///
+/// * `std::io::Result<String>` - The contents of the file as a string, or an error
+///   if the file type is unsupported or if there is an I/O error.
+///
+/// # Errors
+///
+/// This function will return an error if the file type is unsupported or if there
+/// is an I/O error.
+pub fn read_to_string(path: impl AsRef<Path>) -> std::io::Result<String> {
+    match DocumentType::detect(&path)? {
+        Some(DocumentType::Xml) => read_xml_file(path),
+        Some(DocumentType::Text | DocumentType::Json | DocumentType::Code(_)) => {
+            read_text_file(path)
+        }
+        Some(DocumentType::Markdown) => Ok(MarkdownReader::new().read(path)?.into_text()),
+        Some(DocumentType::Epub) => Ok(EpubReader::new().read(path)?.into_text()),
+        Some(DocumentType::Docx) => Ok(OfficeReader::new(OfficeFormat::Docx)
+            .read(path)?
+            .into_text()),
+        Some(DocumentType::Odt) => Ok(OfficeReader::new(OfficeFormat::Odt).read(path)?.into_text()),
+        Some(DocumentType::Jsonl) => read_record_file(path, RecordFormat::Jsonl),
+        Some(DocumentType::Csv) => read_record_file(path, RecordFormat::Csv),
+        Some(DocumentType::Trec) => read_record_file(path, RecordFormat::Trec),
+        Some(DocumentType::MediaWiki) => read_record_file(path, RecordFormat::MediaWiki),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Unsupported file type",
+        )),
+    }
+}
+
+/// Reads an XML file and returns its contents (characters) as a string.
+fn read_xml_file(path: impl AsRef<Path>) -> std::io::Result<String> {
+    let reader = compress::open(path)?;
+    Ok(xml::extract_text(reader).into_text())
+}
+
+/// Reads a text file and returns its contents as a string, transcoded
+/// to UTF-8. Binary files are not read, and an error is returned.
+fn read_text_file(path: impl AsRef<Path>) -> std::io::Result<String> {
+    Ok(encoding::read_text(path)?.0)
+}
+
+/// Reads a file that holds many records and returns the text of its
+/// records, one record after another.
+fn read_record_file(path: impl AsRef<Path>, format: RecordFormat) -> std::io::Result<String> {
+    let texts = RecordReader::new(format)
+        .read(path)?
+        .into_iter()
+        .map(|record| record.into_parts().1)
+        .collect::<Vec<_>>();
+    Ok(texts.join("\n"))
+}
+
+/// Reads the entry of a zip archive with the specified name into a string.
+pub(crate) fn read_zip_entry<R: Read + Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> std::io::Result<String> {
+    let mut entry = archive.by_name(name)?;
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_reader() {
+        let options = ReadOptions::default();
+        assert!(options.record_reader(DocumentType::Jsonl).is_some());
+        assert!(options.record_reader(DocumentType::Json).is_none());
+        assert!(options.record_reader(DocumentType::Markdown).is_none());
+        let options = ReadOptions {
+            json_records: true,
+            ..ReadOptions::default()
+        };
+        assert!(options.record_reader(DocumentType::Json).is_some());
+    }
+}
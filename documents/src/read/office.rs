@@ -0,0 +1,278 @@
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+use xml::reader::XmlEvent;
+use xml::EventReader;
+use zip::ZipArchive;
+
+use crate::core::field::Date;
+use crate::read::compress;
+use crate::read::read_zip_entry;
+
+/// The format of an office document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OfficeFormat {
+    /// Office Open XML word processing document (`.docx`).
+    Docx,
+    /// OpenDocument text document (`.odt`).
+    Odt,
+}
+
+impl OfficeFormat {
+    /// Returns the name of the archive entry with the body of the document.
+    fn content_entry(&self) -> &'static str {
+        match self {
+            Self::Docx => "word/document.xml",
+            Self::Odt => "content.xml",
+        }
+    }
+
+    /// Returns the name of the archive entry with the document properties.
+    fn properties_entry(&self) -> &'static str {
+        match self {
+            Self::Docx => "docProps/core.xml",
+            Self::Odt => "meta.xml",
+        }
+    }
+}
+
+/// The text and properties extracted from an office document.
+#[derive(Debug, Default)]
+pub struct OfficeDocument {
+    text: String,
+    title: Option<String>,
+    author: Option<String>,
+    modified: Option<Date>,
+}
+
+impl OfficeDocument {
+    /// Returns the text of the document, one paragraph per line.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the title of the document, if it is set.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the creator of the document, if it is set.
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// Returns the date that the document was last modified, if it is set.
+    pub fn modified(&self) -> Option<Date> {
+        self.modified
+    }
+
+    /// Consumes the document and returns its text.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// A reader for DOCX and ODT documents.
+///
+/// Both formats are zip archives of XML files. The text is extracted
+/// from the body of the document, keeping paragraph breaks, and the
+/// title, creator and modification date from its core properties.
+#[derive(Debug, Clone)]
+pub struct OfficeReader {
+    format: OfficeFormat,
+}
+
+impl OfficeReader {
+    /// Creates a new reader for documents of the specified format.
+    pub fn new(format: OfficeFormat) -> Self {
+        Self { format }
+    }
+
+    /// Reads the office document at the specified path.
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<OfficeDocument> {
+        let path = path.as_ref();
+        if compress::is_plain(path) {
+            self.read_from(BufReader::new(File::open(path)?))
+        } else {
+            // Zip archives are read by seeking, so they are read into memory.
+            self.read_from(Cursor::new(compress::read(path)?))
+        }
+    }
+
+    /// Reads an office document from a reader.
+    pub fn read_from(&self, reader: impl Read + Seek) -> io::Result<OfficeDocument> {
+        let mut archive = ZipArchive::new(reader)?;
+        let content = read_zip_entry(&mut archive, self.format.content_entry())?;
+        let mut doc = OfficeDocument {
+            text: self.extract_text(&content),
+            ..Default::default()
+        };
+        // Properties are optional, so a document without them is still read.
+        if let Ok(properties) = read_zip_entry(&mut archive, self.format.properties_entry()) {
+            self.extract_properties(&properties, &mut doc);
+        }
+        Ok(doc)
+    }
+
+    /// Extracts the text of the body of the document.
+    ///
+    /// In DOCX, text is only found in `<w:t>` elements, while other
+    /// elements, e.g. field instructions, are skipped, and tabs are only
+    /// the `<w:tab>` elements of runs, not the tab stops of paragraph
+    /// properties. In ODT, all the
+    /// text of the body is kept, and `<text:s>` elements stand for
+    /// one or more spaces.
+    fn extract_text(&self, xml: &str) -> String {
+        let mut text = String::new();
+        let mut in_text = false;
+        let mut in_run = false;
+        for event in EventReader::new(xml.as_bytes()).into_iter().flatten() {
+            match event {
+                XmlEvent::StartElement {
+                    name, attributes, ..
+                } => match (self.format, name.local_name.as_str()) {
+                    (OfficeFormat::Docx, "t") => in_text = true,
+                    (OfficeFormat::Docx, "r") => in_run = true,
+                    (OfficeFormat::Odt, "body") => in_text = true,
+                    (OfficeFormat::Docx, "tab") if in_run => text.push('\t'),
+                    (OfficeFormat::Odt, "tab") => text.push('\t'),
+                    (OfficeFormat::Docx, "br" | "cr") | (OfficeFormat::Odt, "line-break") => {
+                        text.push('\n')
+                    }
+                    (OfficeFormat::Odt, "s") => {
+                        let count = attributes
+                            .iter()
+                            .find(|attr| attr.name.local_name == "c")
+                            .and_then(|attr| attr.value.parse().ok())
+                            .unwrap_or(1);
+                        text.extend(std::iter::repeat_n(' ', count));
+                    }
+                    _ => {}
+                },
+                XmlEvent::EndElement { name } => match (self.format, name.local_name.as_str()) {
+                    (OfficeFormat::Docx, "t") | (OfficeFormat::Odt, "body") => in_text = false,
+                    (OfficeFormat::Docx, "r") => in_run = false,
+                    (OfficeFormat::Docx, "p") | (OfficeFormat::Odt, "p" | "h") => text.push('\n'),
+                    _ => {}
+                },
+                XmlEvent::Characters(chars) | XmlEvent::Whitespace(chars) if in_text => {
+                    text.push_str(&chars)
+                }
+                _ => {}
+            }
+        }
+        text
+    }
+
+    /// Extracts the title, the creator and the modification date from
+    /// the properties of the document.
+    fn extract_properties(&self, xml: &str, doc: &mut OfficeDocument) {
+        let mut element = String::new();
+        let mut creator = None;
+        let mut initial_creator = None;
+        for event in EventReader::new(xml.as_bytes()).into_iter().flatten() {
+            match event {
+                XmlEvent::StartElement { name, .. } => element = name.local_name,
+                XmlEvent::EndElement { .. } => element.clear(),
+                XmlEvent::Characters(chars) => {
+                    let value = chars.trim().to_string();
+                    match element.as_str() {
+                        "title" => doc.title = Some(value),
+                        "creator" => creator = Some(value),
+                        "initial-creator" => initial_creator = Some(value),
+                        "modified" | "date" => doc.modified = value.parse().ok(),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        // In ODT, `dc:creator` is the last person that edited the document.
+        doc.author = initial_creator.or(creator);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    use super::*;
+
+    fn build_archive(files: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, contents) in files {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(contents.as_bytes()).unwrap();
+        }
+        Cursor::new(writer.finish().unwrap().into_inner())
+    }
+
+    #[test]
+    fn test_read_docx() {
+        let document = r#"<?xml version="1.0"?>
+            <w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+              <w:body>
+                <w:p><w:r><w:t>Search</w:t></w:r><w:r><w:t xml:space="preserve">ine spec</w:t></w:r></w:p>
+                <w:p><w:r><w:instrText>PAGE</w:instrText><w:t>Second</w:t><w:tab/><w:t>para</w:t></w:r></w:p>
+                <w:p><w:pPr><w:tabs><w:tab w:val="left" w:pos="720"/></w:tabs></w:pPr><w:r><w:t>Third</w:t></w:r></w:p>
+              </w:body>
+            </w:document>"#;
+        let core = r#"<?xml version="1.0"?>
+            <cp:coreProperties
+                xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
+                xmlns:dc="http://purl.org/dc/elements/1.1/"
+                xmlns:dcterms="http://purl.org/dc/terms/">
+              <dc:title>Spec</dc:title>
+              <dc:creator>Ada</dc:creator>
+              <dcterms:modified>2024-05-02T10:00:00Z</dcterms:modified>
+            </cp:coreProperties>"#;
+        let archive =
+            build_archive(&[("word/document.xml", document), ("docProps/core.xml", core)]);
+        let doc = OfficeReader::new(OfficeFormat::Docx)
+            .read_from(archive)
+            .unwrap();
+        assert_eq!(doc.text(), "Searchine spec\nSecond\tpara\nThird\n");
+        assert_eq!(doc.title(), Some("Spec"));
+        assert_eq!(doc.author(), Some("Ada"));
+        assert_eq!(doc.modified(), Date::new(2024, 5, 2));
+    }
+
+    #[test]
+    fn test_read_odt() {
+        let content = r#"<?xml version="1.0"?>
+            <office:document-content
+                xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+                xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0">
+              <office:body><office:text>
+                <text:h>Overview</text:h>
+                <text:p>Two<text:s text:c="2"/>spaces<text:line-break/>and a break</text:p>
+              </office:text></office:body>
+            </office:document-content>"#;
+        let meta = r#"<?xml version="1.0"?>
+            <office:document-meta
+                xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+                xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0"
+                xmlns:dc="http://purl.org/dc/elements/1.1/">
+              <office:meta>
+                <meta:initial-creator>Ada</meta:initial-creator>
+                <dc:creator>Grace</dc:creator>
+                <dc:date>2023-11-20T08:30:00</dc:date>
+              </office:meta>
+            </office:document-meta>"#;
+        let archive = build_archive(&[("content.xml", content), ("meta.xml", meta)]);
+        let doc = OfficeReader::new(OfficeFormat::Odt)
+            .read_from(archive)
+            .unwrap();
+        assert!(doc.text().contains("Overview\n"));
+        assert!(doc.text().contains("Two  spaces\nand a break\n"));
+        assert_eq!(doc.title(), None);
+        assert_eq!(doc.author(), Some("Ada"));
+        assert_eq!(doc.modified(), Date::new(2023, 11, 20));
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::vec;
+
+use crate::core::field::FieldValue;
+use crate::read::compress;
+use crate::read::frontmatter::text_field;
+use crate::read::mediawiki::MediaWikiRecords;
+use crate::read::trec::TrecRecords;
+
+/// The format of a file that holds many records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// One JSON object per line (`.jsonl`, `.ndjson`).
+    Jsonl,
+    /// Comma-separated values with a header row (`.csv`).
+    Csv,
+    /// A JSON array of objects, or a single object (`.json`).
+    Json,
+    /// TREC SGML, with a `<DOC>` element per document (`.trec`).
+    Trec,
+    /// A MediaWiki XML dump, with a `<page>` element per article.
+    MediaWiki,
+}
+
+/// A single record of a multi-record file.
+///
+/// The key identifies the record within its file. It is the value of
+/// the ID field of the record, or its number, starting from 1, if the
+/// record has no ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    key: String,
+    text: String,
+    fields: BTreeMap<String, FieldValue>,
+}
+
+impl Record {
+    /// Creates a new record with the specified key, text and fields.
+    pub fn new(key: String, text: String, fields: BTreeMap<String, FieldValue>) -> Self {
+        Self { key, text, fields }
+    }
+
+    /// Returns the key of the record within its file.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the text of the record, one text field per line.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the fields of the record that are not part of its text.
+    pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+        &self.fields
+    }
+
+    /// Consumes the record and returns its key, text and fields.
+    pub fn into_parts(self) -> (String, String, BTreeMap<String, FieldValue>) {
+        (self.key, self.text, self.fields)
+    }
+}
+
+/// An iterator over the records of a file.
+///
+/// The records of TREC and MediaWiki files, which are usually large,
+/// are read one at a time, while the records of other files are read
+/// all at once.
+pub enum Records {
+    Parsed(vec::IntoIter<Record>),
+    Trec(TrecRecords<Box<dyn BufRead>>),
+    MediaWiki(Box<MediaWikiRecords<Box<dyn BufRead>>>),
+}
+
+impl Iterator for Records {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Parsed(records) => records.next().map(Ok),
+            Self::Trec(records) => records.next(),
+            Self::MediaWiki(records) => records.next(),
+        }
+    }
+}
+
+/// A reader that splits files that hold many records, e.g. JSONL, CSV
+/// and JSON files, into records.
+///
+/// The values of the text fields of each record make up its text, and
+/// the rest of its values are kept as fields, e.g. to filter by. If no
+/// text fields are specified, all the text values of the record, other
+/// than its ID, are taken as its text.
+///
+/// The records of TREC and MediaWiki files are keyed by the identifiers
+/// of the collection, i.e. the `DOCNO` of TREC documents and the ID of
+/// MediaWiki pages, and the ID and text fields do not apply to them.
+#[derive(Debug, Clone)]
+pub struct RecordReader {
+    format: RecordFormat,
+    id_field: String,
+    text_fields: Vec<String>,
+}
+
+impl RecordReader {
+    /// Creates a new reader for files of the specified format. By default,
+    /// the ID of a record is its `id` field.
+    pub fn new(format: RecordFormat) -> Self {
+        Self {
+            format,
+            id_field: "id".to_string(),
+            text_fields: Vec::new(),
+        }
+    }
+
+    /// Sets the name of the field with the ID of each record.
+    pub fn with_id_field(mut self, id_field: &str) -> Self {
+        self.id_field = id_field.to_lowercase();
+        self
+    }
+
+    /// Sets the names of the fields that make up the text of each record.
+    pub fn with_text_fields(mut self, text_fields: &[String]) -> Self {
+        self.text_fields = text_fields.iter().map(|f| f.to_lowercase()).collect();
+        self
+    }
+
+    /// Reads the records of the file at the specified path.
+    pub fn read(&self, path: impl AsRef<Path>) -> io::Result<Vec<Record>> {
+        self.records(path)?.collect()
+    }
+
+    /// Returns an iterator over the records of the file at the specified
+    /// path.
+    pub fn records(&self, path: impl AsRef<Path>) -> io::Result<Records> {
+        let records = match self.format {
+            RecordFormat::Trec => Records::Trec(TrecRecords::new(compress::open(path)?)),
+            RecordFormat::MediaWiki => {
+                Records::MediaWiki(Box::new(MediaWikiRecords::new(compress::open(path)?)))
+            }
+            _ => Records::Parsed(self.parse(&compress::read_to_string(path)?)?.into_iter()),
+        };
+        Ok(records)
+    }
+
+    /// Splits the contents of a file into records.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a line of a JSONL file, the JSON document or
+    /// a row of a CSV file is malformed.
+    pub fn parse(&self, contents: &str) -> io::Result<Vec<Record>> {
+        let records = match self.format {
+            RecordFormat::Jsonl => contents
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| Ok(json_values(serde_json::from_str(line)?)))
+                .collect::<io::Result<Vec<_>>>()?,
+            RecordFormat::Json => match serde_json::from_str(contents)? {
+                serde_json::Value::Array(items) => items.into_iter().map(json_values).collect(),
+                value => vec![json_values(value)],
+            },
+            RecordFormat::Csv => {
+                let mut reader = csv::Reader::from_reader(contents.as_bytes());
+                let headers = reader.headers()?.clone();
+                reader
+                    .records()
+                    .map(|row| {
+                        let values = headers
+                            .iter()
+                            .zip(row?.iter())
+                            .filter(|(_, cell)| !cell.trim().is_empty())
+                            .map(|(name, cell)| (name.to_string(), csv_field(cell.trim())))
+                            .collect();
+                        Ok(values)
+                    })
+                    .collect::<io::Result<Vec<_>>>()?
+            }
+            RecordFormat::Trec => return TrecRecords::new(contents.as_bytes()).collect(),
+            RecordFormat::MediaWiki => return MediaWikiRecords::new(contents.as_bytes()).collect(),
+        };
+        Ok(records
+            .into_iter()
+            .enumerate()
+            .map(|(idx, values)| self.make_record(idx + 1, values))
+            .collect())
+    }
+
+    /// Separates the values of a record into its text and its fields.
+    fn make_record(&self, number: usize, values: Vec<(String, FieldValue)>) -> Record {
+        let mut fields = values
+            .into_iter()
+            .map(|(name, value)| (name.to_lowercase(), value))
+            .collect::<BTreeMap<_, _>>();
+        let key = fields
+            .get(&self.id_field)
+            .map_or_else(|| number.to_string(), FieldValue::to_string);
+
+        let text_fields = if self.text_fields.is_empty() {
+            fields
+                .iter()
+                .filter(|(name, value)| **name != self.id_field && value.as_text().is_some())
+                .map(|(name, _)| name.clone())
+                .collect()
+        } else {
+            self.text_fields.clone()
+        };
+        let text = text_fields
+            .iter()
+            .filter_map(|name| fields.remove(name))
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        Record { key, text, fields }
+    }
+}
+
+/// Returns the named values of a JSON object. Other JSON values have
+/// no named values.
+fn json_values(value: serde_json::Value) -> Vec<(String, FieldValue)> {
+    match value {
+        serde_json::Value::Object(object) => object
+            .into_iter()
+            .filter_map(|(name, value)| Some((name, json_field(value)?)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Converts a JSON value to a field. Nested objects are not supported.
+fn json_field(value: serde_json::Value) -> Option<FieldValue> {
+    use serde_json::Value;
+
+    match value {
+        Value::String(text) => Some(text_field(text)),
+        Value::Number(number) => Some(FieldValue::Number(number.as_f64()?)),
+        Value::Bool(bool) => Some(FieldValue::Bool(bool)),
+        Value::Array(items) => {
+            let items = items
+                .into_iter()
+                .filter_map(|item| match json_field(item)? {
+                    FieldValue::List(_) => None,
+                    item => Some(item.to_string()),
+                })
+                .collect();
+            Some(FieldValue::List(items))
+        }
+        Value::Null | Value::Object(_) => None,
+    }
+}
+
+/// Converts a CSV cell to a field. Cells are numbers or booleans only
+/// if they are written the way such values are displayed, so that e.g.
+/// an ID with leading zeros remains text.
+fn csv_field(cell: &str) -> FieldValue {
+    match (cell.parse::<f64>(), cell.parse::<bool>()) {
+        (Ok(number), _) if number.to_string() == cell => FieldValue::Number(number),
+        (_, Ok(bool)) => FieldValue::Bool(bool),
+        _ => text_field(cell.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jsonl_records() {
+        let contents = r#"{"id": "T-1", "subject": "Login fails", "body": "Cannot sign in", "priority": 2}
+
+{"subject": "Slow search", "tags": ["perf", "search"]}
+"#;
+        let records = RecordReader::new(RecordFormat::Jsonl)
+            .parse(contents)
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].key(), "T-1");
+        assert_eq!(records[0].text(), "Cannot sign in\nLogin fails");
+        assert_eq!(records[0].fields()["priority"], FieldValue::Number(2.0));
+        assert_eq!(
+            records[0].fields()["id"],
+            FieldValue::Text("T-1".to_string())
+        );
+        assert_eq!(records[1].key(), "2");
+        assert_eq!(records[1].text(), "Slow search");
+        assert_eq!(
+            records[1].fields()["tags"],
+            FieldValue::List(vec!["perf".to_string(), "search".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_csv_records() {
+        let contents = "Ticket,Subject,Body,Opened\n\
+            0042,Crash,\"Panics on start, always\",2024-03-01\n";
+        let text_fields = ["body".to_string()];
+        let records = RecordReader::new(RecordFormat::Csv)
+            .with_id_field("ticket")
+            .with_text_fields(&text_fields)
+            .parse(contents)
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].key(), "0042");
+        assert_eq!(records[0].text(), "Panics on start, always");
+        assert_eq!(
+            records[0].fields()["subject"],
+            FieldValue::Text("Crash".to_string())
+        );
+        assert!(records[0].fields()["opened"].as_date().is_some());
+    }
+
+    #[test]
+    fn test_json_array_records() {
+        let contents = r#"[{"id": 7, "text": "first"}, {"id": 8, "text": "second"}]"#;
+        let records = RecordReader::new(RecordFormat::Json)
+            .parse(contents)
+            .unwrap();
+        let keys = records.iter().map(Record::key).collect::<Vec<_>>();
+        assert_eq!(keys, ["7", "8"]);
+        assert_eq!(records[1].text(), "second");
+        assert!(RecordReader::new(RecordFormat::Json).parse("[1,").is_err());
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
+
+use crate::core::field::FieldValue;
+use crate::read::records::Record;
+
+/// The elements of a TREC document whose text is its title.
+const TITLE_TAGS: &[&str] = &["HEAD", "HEADLINE", "HL", "TITLE"];
+
+/// A streaming reader of the documents of a TREC SGML file.
+///
+/// Each `<DOC>` element of the file is read as a record, whose key is
+/// the identifier in its `<DOCNO>` element. The text of the record is
+/// the text of the rest of its elements, without the tags.
+///
+/// # Examples
+///
+/// ```
+/// use documents::read::trec::TrecRecords;
+///
+/// let sgml = "<DOC>\n<DOCNO> FT911-1 </DOCNO>\n<TEXT>Markets rallied.</TEXT>\n</DOC>\n";
+/// let records = TrecRecords::new(sgml.as_bytes())
+///     .collect::<std::io::Result<Vec<_>>>()
+///     .unwrap();
+/// assert_eq!(records[0].key(), "FT911-1");
+/// ```
+pub struct TrecRecords<R> {
+    reader: R,
+    buffer: String,
+    done: bool,
+}
+
+impl<R: BufRead> TrecRecords<R> {
+    /// Creates a new reader of the documents of a TREC file.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            done: false,
+        }
+    }
+
+    /// Reads lines until the buffer holds a whole document, and returns
+    /// the text between its `<DOC>` and `</DOC>` tags.
+    fn next_document(&mut self) -> io::Result<Option<String>> {
+        loop {
+            if let Some(start) = self.buffer.find("<DOC>") {
+                if let Some(end) = self.buffer[start..].find("</DOC>") {
+                    let document = self.buffer[start + "<DOC>".len()..start + end].to_string();
+                    self.buffer.drain(..start + end + "</DOC>".len());
+                    return Ok(Some(document));
+                }
+            }
+            if self.reader.read_line(&mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for TrecRecords<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            match self.next_document() {
+                Ok(Some(document)) => {
+                    // Documents without an identifier cannot be reported,
+                    // so they are skipped.
+                    if let Some(record) = parse_document(&document) {
+                        return Some(Ok(record));
+                    }
+                }
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+/// Parses the contents of a `<DOC>` element into a record.
+fn parse_document(document: &str) -> Option<Record> {
+    let docno = element_text(document, "DOCNO")?.trim().to_string();
+    if docno.is_empty() {
+        return None;
+    }
+    let mut fields = BTreeMap::new();
+    if let Some(title) = TITLE_TAGS
+        .iter()
+        .find_map(|tag| element_text(document, tag))
+        .map(strip_tags)
+        .filter(|title| !title.is_empty())
+    {
+        fields.insert("title".to_string(), FieldValue::Text(title));
+    }
+
+    let docno_end = document.find("</DOCNO>")? + "</DOCNO>".len();
+    let docno_start = document.find("<DOCNO>")?;
+    let rest = format!("{}\n{}", &document[..docno_start], &document[docno_end..]);
+    Some(Record::new(docno, strip_tags(&rest), fields))
+}
+
+/// Returns the text of the first element with the specified tag.
+fn element_text<'a>(document: &'a str, tag: &str) -> Option<&'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let start = document.find(&open)? + open.len();
+    let end = start + document[start..].find(&close)?;
+    Some(&document[start..end])
+}
+
+/// Removes the SGML tags and comments from the text, and replaces the
+/// common character entities.
+fn strip_tags(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        stripped.push_str(&rest[..start]);
+        let close = if rest[start..].starts_with("<!--") {
+            "-->"
+        } else {
+            ">"
+        };
+        match rest[start..].find(close) {
+            Some(end) => {
+                stripped.push(' ');
+                rest = &rest[start + end + close.len()..];
+            }
+            None => {
+                rest = "";
+            }
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trec_records() {
+        let sgml = "<DOC>\n<DOCNO> LA010189-0001 </DOCNO>\n\
+            <HEADLINE><P>Rates &amp; bonds</P></HEADLINE>\n\
+            <TEXT>\n<P>Bond yields fell.</P>\n<!-- note -->\n</TEXT>\n</DOC>\n\
+            <DOC><TEXT>No identifier.</TEXT></DOC>\n\
+            <DOC>\n<DOCNO>LA010189-0002</DOCNO>\n<TEXT>Stocks rose.</TEXT>\n</DOC>\n";
+        let records = TrecRecords::new(sgml.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].key(), "LA010189-0001");
+        assert_eq!(
+            records[0].fields()["title"],
+            FieldValue::Text("Rates & bonds".to_string())
+        );
+        assert!(records[0].text().contains("Bond yields fell."));
+        assert!(!records[0].text().contains("note"));
+        assert!(!records[0].text().contains("LA010189"));
+        assert_eq!(records[1].key(), "LA010189-0002");
+        assert_eq!(records[1].text(), "Stocks rose.");
+    }
+}
@@ -0,0 +1,112 @@
+use std::io::Read;
+
+use xml::reader::XmlEvent;
+use xml::{EventReader, ParserConfig};
+
+/// HTML entities that are common in XHTML documents, but are not
+/// defined by XML itself.
+const HTML_ENTITIES: &[(&str, &str)] = &[
+    ("nbsp", " "),
+    ("ndash", "–"),
+    ("mdash", "—"),
+    ("hellip", "…"),
+    ("lsquo", "‘"),
+    ("rsquo", "’"),
+    ("ldquo", "“"),
+    ("rdquo", "”"),
+    ("copy", "©"),
+    ("shy", ""),
+];
+
+/// The text extracted from an XML document.
+#[derive(Debug, Default)]
+pub struct XmlText {
+    text: String,
+    title: Option<String>,
+    heading: Option<String>,
+}
+
+impl XmlText {
+    /// Returns the characters of the document, separated by spaces.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the text of the `<title>` element, if there is one.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Returns the text of the first `<h1>`, `<h2>` or `<h3>` element,
+    /// if there is one.
+    pub fn heading(&self) -> Option<&str> {
+        self.heading.as_deref()
+    }
+
+    /// Consumes the extracted text and returns the characters.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+}
+
+/// Extracts the characters of an XML document.
+///
+/// Parsing stops at the first malformed part of the document, and
+/// the text up to that part is returned.
+pub fn extract_text(reader: impl Read) -> XmlText {
+    let config = HTML_ENTITIES
+        .iter()
+        .fold(ParserConfig::new(), |config, (name, value)| {
+            config.add_entity(*name, *value)
+        });
+    let reader = EventReader::new_with_config(reader, config);
+
+    let mut xml_text = XmlText::default();
+    // The element whose text is captured, and the offset where it starts.
+    let mut capture: Option<(String, usize)> = None;
+    for event in reader.into_iter().flatten() {
+        match event {
+            XmlEvent::StartElement { name, .. } if capture.is_none() => {
+                let name = name.local_name.to_lowercase();
+                let is_title = name == "title" && xml_text.title.is_none();
+                let is_heading =
+                    matches!(name.as_str(), "h1" | "h2" | "h3") && xml_text.heading.is_none();
+                if is_title || is_heading {
+                    capture = Some((name, xml_text.text.len()));
+                }
+            }
+            XmlEvent::EndElement { name } => {
+                let name = name.local_name.to_lowercase();
+                if let Some((_, offset)) = capture.take_if(|(element, _)| *element == name) {
+                    let text = xml_text.text[offset..].trim().to_string();
+                    match name.as_str() {
+                        "title" => xml_text.title = Some(text),
+                        _ => xml_text.heading = Some(text),
+                    }
+                }
+            }
+            XmlEvent::Characters(string) | XmlEvent::CData(string) => {
+                xml_text.text.push_str(&string);
+                xml_text.text.push(' ');
+            }
+            _ => {}
+        }
+    }
+    xml_text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_xhtml_text() {
+        let xhtml = "<html><head><title>Chapter 1</title></head>\
+            <body><h1>Beginnings</h1><p>It&nbsp;was a <em>dark</em> night.</p></body></html>";
+        let xml_text = extract_text(xhtml.as_bytes());
+        assert_eq!(xml_text.title(), Some("Chapter 1"));
+        assert_eq!(xml_text.heading(), Some("Beginnings"));
+        assert!(xml_text.text().contains("It was a"));
+        assert!(xml_text.text().contains("dark"));
+    }
+}
@@ -0,0 +1,82 @@
+use std::ops::Range;
+
+use crate::split::traits::TextSplit;
+
+/// A splitter of text into chunks of a fixed number of characters, that
+/// overlap by a fixed number of characters.
+///
+/// # Examples
+///
+/// ```
+/// use documents::split::{CharacterTextSplitter, TextSplit};
+///
+/// let splitter = CharacterTextSplitter::new(4, 1);
+/// let chunks = splitter.split_text("abcdefghij".to_string());
+/// assert_eq!(chunks, ["abcd", "defg", "ghij"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharacterTextSplitter {
+    chunk_len: usize,
+    chunk_overlap: usize,
+}
+
+impl CharacterTextSplitter {
+    /// Creates a new splitter into chunks of `chunk_len` characters, that
+    /// overlap by `chunk_overlap` characters. The overlap is reduced to
+    /// less than the length of the chunks, so that the chunks advance.
+    pub fn new(chunk_len: usize, chunk_overlap: usize) -> Self {
+        let chunk_len = chunk_len.max(1);
+        Self {
+            chunk_len,
+            chunk_overlap: chunk_overlap.min(chunk_len - 1),
+        }
+    }
+}
+
+impl TextSplit for CharacterTextSplitter {
+    fn chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    fn chunk_overlap(&self) -> usize {
+        self.chunk_overlap
+    }
+
+    fn split_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        // The byte offsets of the characters, and of the end of the text.
+        let bounds = text
+            .char_indices()
+            .map(|(idx, _)| idx)
+            .chain(std::iter::once(text.len()))
+            .collect::<Vec<_>>();
+        let len = bounds.len() - 1;
+        let step = self.chunk_len - self.chunk_overlap;
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start < len {
+            let end = (start + self.chunk_len).min(len);
+            ranges.push(bounds[start]..bounds[end]);
+            if end == len {
+                break;
+            }
+            start += step;
+        }
+        ranges
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_characters() {
+        let splitter = CharacterTextSplitter::new(3, 0);
+        assert_eq!(splitter.split_text("αβγδε".to_string()), ["αβγ", "δε"]);
+        assert!(splitter.split_ranges("").is_empty());
+
+        let splitter = CharacterTextSplitter::new(2, 5);
+        assert_eq!(splitter.chunk_overlap(), 1);
+        assert_eq!(splitter.split_ranges("abcd"), [0..2, 1..3, 2..4]);
+    }
+}
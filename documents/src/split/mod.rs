@@ -1 +1,67 @@
-mod traits;
+use serde::{Deserialize, Serialize};
+
+pub use crate::split::character::CharacterTextSplitter;
+pub use crate::split::recursive::RecursiveTextSplitter;
+pub use crate::split::token::TokenTextSplitter;
+pub use crate::split::traits::{TextSplit, TokenTextSplit};
+
+pub mod character;
+pub mod recursive;
+pub mod token;
+pub mod traits;
+
+/// The method that documents are split into passages with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SplitMethod {
+    /// Chunks of a fixed number of characters.
+    Character,
+    /// Chunks of paragraphs, sentences or words, of up to a number of
+    /// characters.
+    Recursive,
+    /// Chunks of a fixed number of tokens.
+    Token,
+}
+
+/// Options that control how documents are split into passages.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SplitOptions {
+    /// The method that documents are split with.
+    pub method: SplitMethod,
+    /// The maximum length of each passage, in characters, or in tokens
+    /// for the `token` method.
+    pub chunk_len: usize,
+    /// The length of the overlap between consecutive passages, in the
+    /// same units as `chunk_len`.
+    pub chunk_overlap: usize,
+}
+
+impl Default for SplitOptions {
+    fn default() -> Self {
+        Self {
+            method: SplitMethod::Recursive,
+            chunk_len: 1000,
+            chunk_overlap: 100,
+        }
+    }
+}
+
+impl SplitOptions {
+    /// Returns a text splitter configured with the options.
+    pub fn splitter(&self) -> Box<dyn TextSplit> {
+        match self.method {
+            SplitMethod::Character => Box::new(CharacterTextSplitter::new(
+                self.chunk_len,
+                self.chunk_overlap,
+            )),
+            SplitMethod::Recursive => Box::new(RecursiveTextSplitter::new(
+                self.chunk_len,
+                self.chunk_overlap,
+            )),
+            SplitMethod::Token => {
+                Box::new(TokenTextSplitter::new(self.chunk_len, self.chunk_overlap))
+            }
+        }
+    }
+}
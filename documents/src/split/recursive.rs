@@ -0,0 +1,163 @@
+use std::ops::Range;
+
+use crate::split::character::CharacterTextSplitter;
+use crate::split::traits::TextSplit;
+
+/// The separators that text is split at by default: paragraphs, lines,
+/// sentences and words.
+const DEFAULT_SEPARATORS: [&str; 6] = ["\n\n", "\n", ". ", "? ", "! ", " "];
+
+/// A splitter of text at a list of separators, from the coarsest to the
+/// finest, e.g. paragraphs, then sentences, then words.
+///
+/// The text is split at the first separator, and the pieces that are
+/// longer than a chunk are split at the next separator, and so on. Pieces
+/// that no separator splits are split at characters. The pieces are then
+/// merged into chunks of up to `chunk_len` characters, and each chunk
+/// starts with up to `chunk_overlap` characters of the pieces that end the
+/// previous chunk. Chunks are trimmed of whitespace.
+///
+/// # Examples
+///
+/// ```
+/// use documents::split::{RecursiveTextSplitter, TextSplit};
+///
+/// let splitter = RecursiveTextSplitter::new(20, 0);
+/// let text = "First paragraph.\n\nSecond one. It is longer.";
+/// let chunks = splitter.split_text(text.to_string());
+/// assert_eq!(chunks, ["First paragraph.", "Second one.", "It is longer."]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RecursiveTextSplitter {
+    chunk_len: usize,
+    chunk_overlap: usize,
+    separators: Vec<String>,
+}
+
+impl RecursiveTextSplitter {
+    /// Creates a new splitter into chunks of up to `chunk_len` characters,
+    /// that overlap by up to `chunk_overlap` characters, at the default
+    /// separators. The overlap is reduced to less than the length of the
+    /// chunks, so that the chunks advance.
+    pub fn new(chunk_len: usize, chunk_overlap: usize) -> Self {
+        let chunk_len = chunk_len.max(1);
+        Self {
+            chunk_len,
+            chunk_overlap: chunk_overlap.min(chunk_len - 1),
+            separators: DEFAULT_SEPARATORS.map(str::to_string).to_vec(),
+        }
+    }
+
+    /// Sets the separators that the text is split at, from the coarsest
+    /// to the finest.
+    pub fn with_separators(self, separators: Vec<String>) -> Self {
+        Self { separators, ..self }
+    }
+
+    /// Splits the range of the text into contiguous pieces of up to
+    /// `chunk_len` characters, at the coarsest separators that suffice.
+    fn split_pieces(
+        &self,
+        text: &str,
+        range: Range<usize>,
+        separators: &[String],
+    ) -> Vec<Range<usize>> {
+        if text[range.clone()].chars().count() <= self.chunk_len {
+            return vec![range];
+        }
+        let Some((separator, separators)) = separators.split_first() else {
+            return CharacterTextSplitter::new(self.chunk_len, 0)
+                .split_ranges(&text[range.clone()])
+                .into_iter()
+                .map(|piece| piece.start + range.start..piece.end + range.start)
+                .collect();
+        };
+        let mut pieces = Vec::new();
+        let mut start = range.start;
+        // Separators are kept at the end of the piece that they follow.
+        for (idx, _) in text[range.clone()].match_indices(separator.as_str()) {
+            let end = range.start + idx + separator.len();
+            pieces.extend(self.split_pieces(text, start..end, separators));
+            start = end;
+        }
+        if start < range.end {
+            pieces.extend(self.split_pieces(text, start..range.end, separators));
+        }
+        pieces
+    }
+}
+
+impl TextSplit for RecursiveTextSplitter {
+    fn chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    fn chunk_overlap(&self) -> usize {
+        self.chunk_overlap
+    }
+
+    fn split_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let pieces = self.split_pieces(text, 0..text.len(), &self.separators);
+        let lens = pieces
+            .iter()
+            .map(|piece| text[piece.clone()].chars().count())
+            .collect::<Vec<_>>();
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start < pieces.len() {
+            // Merge as many pieces as fit in a chunk, and at least one.
+            let (mut end, mut len) = (start, 0);
+            while end < pieces.len() && (end == start || len + lens[end] <= self.chunk_len) {
+                len += lens[end];
+                end += 1;
+            }
+            ranges.push(trim_range(text, pieces[start].start..pieces[end - 1].end));
+            if end == pieces.len() {
+                break;
+            }
+            // The next chunk starts with the last pieces that fit in the overlap.
+            let (mut next, mut overlap) = (end, 0);
+            while next > start + 1 && overlap + lens[next - 1] <= self.chunk_overlap {
+                next -= 1;
+                overlap += lens[next];
+            }
+            start = next;
+        }
+        ranges.retain(|range| !range.is_empty());
+        ranges
+    }
+}
+
+/// Shrinks the range of the text to exclude leading and trailing whitespace.
+fn trim_range(text: &str, range: Range<usize>) -> Range<usize> {
+    let slice = &text[range.clone()];
+    let start = range.start + (slice.len() - slice.trim_start().len());
+    let end = range.end - (slice.len() - slice.trim_end().len());
+    start..end.max(start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_recursive() {
+        let text = "One two three. Four five six.\n\nSeven eight nine ten eleven twelve.";
+        let splitter = RecursiveTextSplitter::new(30, 0);
+        assert_eq!(
+            splitter.split_text(text.to_string()),
+            [
+                "One two three. Four five six.",
+                "Seven eight nine ten eleven",
+                "twelve."
+            ]
+        );
+
+        let splitter = RecursiveTextSplitter::new(12, 6);
+        let chunks = splitter.split_text("aaa bbb ccc ddd eee".to_string());
+        assert_eq!(chunks, ["aaa bbb ccc", "ccc ddd eee"]);
+
+        let splitter = RecursiveTextSplitter::new(4, 0).with_separators(Vec::new());
+        assert_eq!(splitter.split_text("abcdef".to_string()), ["abcd", "ef"]);
+    }
+}
@@ -0,0 +1,99 @@
+use std::ops::Range;
+
+use crate::split::traits::{TextSplit, TokenTextSplit};
+
+/// A splitter of text into chunks of a fixed number of tokens, that
+/// overlap by a fixed number of tokens. The tokens are the runs of
+/// alphanumeric characters of the text, and each chunk spans the text
+/// from its first token to its last.
+///
+/// # Examples
+///
+/// ```
+/// use documents::split::{TextSplit, TokenTextSplitter};
+///
+/// let splitter = TokenTextSplitter::new(3, 1);
+/// let chunks = splitter.split_text("One, two, three, four, five.".to_string());
+/// assert_eq!(chunks, ["One, two, three", "three, four, five"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TokenTextSplitter {
+    chunk_len: usize,
+    chunk_overlap: usize,
+}
+
+impl TokenTextSplitter {
+    /// Creates a new splitter into chunks of `chunk_len` tokens, that
+    /// overlap by `chunk_overlap` tokens. The overlap is reduced to less
+    /// than the length of the chunks, so that the chunks advance.
+    pub fn new(chunk_len: usize, chunk_overlap: usize) -> Self {
+        let chunk_len = chunk_len.max(1);
+        Self {
+            chunk_len,
+            chunk_overlap: chunk_overlap.min(chunk_len - 1),
+        }
+    }
+}
+
+impl TextSplit for TokenTextSplitter {
+    fn chunk_len(&self) -> usize {
+        self.chunk_len
+    }
+
+    fn chunk_overlap(&self) -> usize {
+        self.chunk_overlap
+    }
+
+    fn split_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let tokens = self.tokens(text);
+        let step = self.chunk_len - self.chunk_overlap;
+        let mut ranges = Vec::new();
+        let mut start = 0;
+        while start < tokens.len() {
+            let end = (start + self.chunk_len).min(tokens.len());
+            ranges.push(tokens[start].start..tokens[end - 1].end);
+            if end == tokens.len() {
+                break;
+            }
+            start += step;
+        }
+        ranges
+    }
+}
+
+impl TokenTextSplit for TokenTextSplitter {
+    fn tokens(&self, text: &str) -> Vec<Range<usize>> {
+        let mut tokens = Vec::new();
+        let mut start = None;
+        for (idx, c) in text.char_indices() {
+            match (c.is_alphanumeric(), start) {
+                (true, None) => start = Some(idx),
+                (false, Some(token_start)) => {
+                    tokens.push(token_start..idx);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        if let Some(token_start) = start {
+            tokens.push(token_start..text.len());
+        }
+        tokens
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_tokens() {
+        let splitter = TokenTextSplitter::new(2, 0);
+        assert_eq!(splitter.tokens("  héllo, wörld!"), [2..8, 10..16]);
+        assert_eq!(
+            splitter.split_text("a b c d e".to_string()),
+            ["a b", "c d", "e"]
+        );
+        assert!(splitter.split_ranges(" ... ").is_empty());
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::{Document, DocumentMetadata};
 
 pub trait TextSplit {
@@ -7,15 +9,49 @@ pub trait TextSplit {
     /// Overlap in characters between chunks
     fn chunk_overlap(&self) -> usize;
 
+    /// Splits the text into chunks, and returns the byte ranges of the
+    /// chunks in the text, in order.
+    fn split_ranges(&self, text: &str) -> Vec<Range<usize>>;
+
     /// Splits the text into multiple components.
-    fn split_text(&self, text: String) -> Vec<String>;
+    fn split_text(&self, text: String) -> Vec<String> {
+        self.split_ranges(&text)
+            .into_iter()
+            .map(|range| text[range].to_string())
+            .collect()
+    }
 
     /// Create documents from the chunks the text was split into.
+    ///
+    /// Each text is split into chunks, and each chunk becomes a document
+    /// with the metadata of its text. The documents are given IDs in the
+    /// order of the chunks, starting from 0.
     fn create_documents(
         &self,
         texts: Vec<String>,
         metadatas: Vec<DocumentMetadata>,
-    ) -> anyhow::Result<Vec<Document>>;
+    ) -> anyhow::Result<Vec<Document>> {
+        anyhow::ensure!(
+            texts.len() == metadatas.len(),
+            "Expected a metadata for each of {} texts, but got {}",
+            texts.len(),
+            metadatas.len()
+        );
+        let mut documents = Vec::new();
+        for (text, metadata) in texts.into_iter().zip(metadatas) {
+            for chunk in self.split_text(text) {
+                let doc_id = documents.len() as u32;
+                documents.push(Document::new(doc_id, chunk, metadata.clone()));
+            }
+        }
+        Ok(documents)
+    }
 }
 
-pub trait TokenTextSplit: TextSplit {}
+/// A splitter of text whose chunk length and overlap are counted in
+/// tokens instead of characters.
+pub trait TokenTextSplit: TextSplit {
+    /// Splits the text into tokens, and returns the byte ranges of the
+    /// tokens in the text, in order.
+    fn tokens(&self, text: &str) -> Vec<Range<usize>>;
+}
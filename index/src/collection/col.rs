@@ -1,12 +1,14 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{self, BufReader, BufWriter};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
-use documents::DocumentId;
+use documents::read::archive::{self, MemberHashes};
+use documents::{DocumentId, FieldValue, Section};
 
 use crate::collection::CollectionEntry;
 
@@ -14,34 +16,108 @@ use crate::collection::CollectionEntry;
 ///
 /// This struct is used to build an in-memory index for multiple documents.
 /// Each document is assigned a unique document ID, and the last time the
-/// document was indexed.
+/// document was indexed. The records of files that hold many records are
+/// documents too, and are assigned their own IDs, and so are the passages
+/// of documents that are split into passages.
 #[derive(Default, Serialize, Deserialize)]
 pub struct Collection {
     root_dir: PathBuf,
     index: HashMap<PathBuf, CollectionEntry>,
+    #[serde(skip)]
+    next_id: DocumentId,
 }
 
 impl Collection {
     /// Creates a new `CorpusIndex` from an iterator of paths.
     pub fn from_paths(iter: impl IntoIterator<Item = PathBuf>) -> io::Result<Self> {
         let mut index = Self::default();
+        let mut hashes = MemberHashes::new();
         for path in iter {
-            index.insert(path)?;
+            index.insert_with_hashes(path, &mut hashes)?;
         }
         Ok(index)
     }
 
     /// Adds a document to the index, and assigns it a unique ID.
+    ///
+    /// The document may be a member of an archive, with a path such as
+    /// `bundle.zip!/docs/intro.md`. Its last modified time is that of the
+    /// archive, and the hash of its contents is stored too.
     pub fn insert(&mut self, document_path: PathBuf) -> io::Result<()> {
+        self.insert_with_hashes(document_path, &mut MemberHashes::new())
+    }
+
+    /// Adds a document to the index, taking the hashes of members of
+    /// archives from the cache.
+    fn insert_with_hashes(
+        &mut self,
+        document_path: PathBuf,
+        hashes: &mut MemberHashes,
+    ) -> io::Result<()> {
         if !self.index.contains_key(&document_path) {
-            let modified = document_path.metadata()?.modified()?;
-            let next_id = self.index.len() as u32;
-            let entry = CollectionEntry::new(next_id, modified);
+            let modified = archive::modified(&document_path)?;
+            let mut entry = CollectionEntry::new(self.next_id(), modified);
+            entry.set_hash(hashes.get(&document_path)?);
             self.index.insert(document_path, entry);
         }
         Ok(())
     }
 
+    /// Adds a record of the document with the specified path, and assigns
+    /// it a unique ID, which is returned. If the path is not found in the
+    /// index, or the document already has a record with the same key,
+    /// `None` is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `document_path` - The path to the document that holds the record.
+    /// * `key` - The key of the record in the document.
+    /// * `fields` - The metadata fields of the record.
+    pub fn insert_record(
+        &mut self,
+        document_path: &PathBuf,
+        key: &str,
+        fields: BTreeMap<String, FieldValue>,
+    ) -> Option<DocumentId> {
+        let next_id = self.next_id;
+        let entry = self.index.get_mut(document_path)?;
+        if entry.record(key).is_some() {
+            return None;
+        }
+        let record =
+            CollectionEntry::new_record(next_id, entry.modified(), key.to_string(), fields);
+        entry.insert_record(record);
+        self.next_id += 1;
+        Some(next_id)
+    }
+
+    /// Adds a passage of the document with the specified path, at the
+    /// specified byte range of its contents, and assigns it a unique ID,
+    /// which is returned. The line of the file that the passage starts on
+    /// is given if the contents of the document keep the lines of its file.
+    /// If the path is not found in the index, `None` is returned.
+    pub fn insert_passage(
+        &mut self,
+        document_path: &PathBuf,
+        range: Range<usize>,
+        line: Option<usize>,
+    ) -> Option<DocumentId> {
+        let next_id = self.next_id;
+        let entry = self.index.get_mut(document_path)?;
+        let (document_id, modified) = (entry.document_id(), entry.modified());
+        let passage = CollectionEntry::new_passage(next_id, document_id, modified, range, line);
+        entry.push_passage(passage);
+        self.next_id += 1;
+        Some(next_id)
+    }
+
+    /// Returns the next unused document ID, and advances it.
+    fn next_id(&mut self) -> DocumentId {
+        let next_id = self.next_id;
+        self.next_id += 1;
+        next_id
+    }
+
     /// Returns true if the index contains a document with the specified path.
     /// Otherwise, it returns false.
     pub fn contains_path(&self, document_path: &PathBuf) -> bool {
@@ -73,6 +149,35 @@ impl Collection {
         Some(self.index.get(document_path)?.modified())
     }
 
+    /// Returns the hash of the contents of the document with the specified
+    /// path, if it is a member of an archive. If the path is not found in
+    /// the index, `None` is returned.
+    pub fn get_hash(&self, document_path: &PathBuf) -> Option<u64> {
+        self.index.get(document_path)?.hash()
+    }
+
+    /// Sets the metadata fields of the document with the specified path.
+    /// If the path is not found in the index, nothing is done.
+    ///
+    /// # Arguments
+    ///
+    /// * `document_path` - The path to the document.
+    /// * `fields` - The metadata fields of the document.
+    pub fn set_fields(&mut self, document_path: &PathBuf, fields: BTreeMap<String, FieldValue>) {
+        if let Some(entry) = self.index.get_mut(document_path) {
+            entry.set_fields(fields);
+        }
+    }
+
+    /// Sets the sections of the document with the specified path, with the
+    /// byte offsets of their headings in its contents. If the path is not
+    /// found in the index, nothing is done.
+    pub fn set_sections(&mut self, document_path: &PathBuf, sections: Vec<Section>) {
+        if let Some(entry) = self.index.get_mut(document_path) {
+            entry.set_sections(sections);
+        }
+    }
+
     /// Removes an index entry with the specified document path.
     ///
     /// # Arguments
@@ -101,7 +206,14 @@ impl Collection {
         let path = path.as_ref();
         let file = File::open(path)?;
         let reader = BufReader::new(file);
-        let index = serde_json::from_reader(reader)?;
+        let mut index: Self = serde_json::from_reader(reader)?;
+        index.next_id = index
+            .index
+            .values()
+            .flat_map(|entry| std::iter::once(entry).chain(entry.children()))
+            .map(|entry| entry.document_id() + 1)
+            .max()
+            .unwrap_or_default();
         Ok(index)
     }
 }
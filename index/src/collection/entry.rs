@@ -1,20 +1,58 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::Range;
 use std::time::SystemTime;
 
 use serde::{Deserialize, Serialize};
 
-use documents::DocumentId;
+use documents::{DocumentId, FieldValue, Language, Section};
 
 /// A struct representing an entry in the corpus index.
 /// It contains the document ID and the last time the document was modified.
 ///
 /// The document ID is a unique identifier for each document in the corpus.
 /// The last modified time is used to determine if the document has been
-/// modified since the last indexing.
+/// modified since the last indexing. The metadata fields of the document,
+/// e.g. its title and tags, are stored once the document has been indexed.
+///
+/// Files that hold many records, e.g. JSONL files, have an entry for each
+/// record, with its own document ID and the key of the record in the file.
+/// The key is the external ID of the record, e.g. the `DOCNO` of a TREC
+/// document, so that results can be reported with it.
+///
+/// Documents that are split into passages have an entry for each passage,
+/// with its own document ID, the ID of the document as its parent, and the
+/// byte range of the passage in the contents of the document. Passages of
+/// plain text and source code also have the line of the file they start on.
+///
+/// The sections of a document, e.g. the chapters of a book, are stored with
+/// the byte offsets of their headings in its contents, so that results can
+/// be shown with their section without reading the document again.
+///
+/// Members of archives are modified when their archive is, so the hash of
+/// the contents of each member is stored too, to tell if it has changed.
 #[derive(Serialize, Deserialize, Clone)]
 pub struct CollectionEntry {
     document_id: DocumentId,
     modified: SystemTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hash: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    key: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    fields: BTreeMap<String, FieldValue>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    records: BTreeMap<String, CollectionEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    parent: Option<DocumentId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    range: Option<Range<usize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    passages: Vec<CollectionEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    sections: Vec<Section>,
 }
 
 impl CollectionEntry {
@@ -24,6 +62,48 @@ impl CollectionEntry {
         Self {
             document_id,
             modified,
+            hash: None,
+            key: None,
+            fields: BTreeMap::new(),
+            records: BTreeMap::new(),
+            parent: None,
+            range: None,
+            line: None,
+            passages: Vec::new(),
+            sections: Vec::new(),
+        }
+    }
+
+    /// Creates a new `CollectionEntry` for the record with the specified
+    /// key, document ID and metadata fields.
+    pub fn new_record(
+        document_id: DocumentId,
+        modified: SystemTime,
+        key: String,
+        fields: BTreeMap<String, FieldValue>,
+    ) -> Self {
+        Self {
+            key: Some(key),
+            fields,
+            ..Self::new(document_id, modified)
+        }
+    }
+
+    /// Creates a new `CollectionEntry` for the passage of the document with
+    /// the specified parent ID, at the specified byte range of its contents,
+    /// and the line of its file that it starts on, if known.
+    pub fn new_passage(
+        document_id: DocumentId,
+        parent: DocumentId,
+        modified: SystemTime,
+        range: Range<usize>,
+        line: Option<usize>,
+    ) -> Self {
+        Self {
+            parent: Some(parent),
+            range: Some(range),
+            line,
+            ..Self::new(document_id, modified)
         }
     }
 
@@ -33,10 +113,119 @@ impl CollectionEntry {
         self.modified
     }
 
+    /// Returns the hash of the contents of the document, if it is a member
+    /// of an archive.
+    pub fn hash(&self) -> Option<u64> {
+        self.hash
+    }
+
+    /// Sets the hash of the contents of the document.
+    pub fn set_hash(&mut self, hash: Option<u64>) {
+        self.hash = hash;
+    }
+
     /// Returns the document ID associated with the document.
     pub fn document_id(&self) -> DocumentId {
         self.document_id
     }
+
+    /// Returns the key of the record in its file, if the entry is a record.
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Returns the entries of the records of the document, if it is a
+    /// file that holds many records.
+    pub fn records(&self) -> impl Iterator<Item = &CollectionEntry> {
+        self.records.values()
+    }
+
+    /// Returns the document ID of the document that the entry is a passage
+    /// of, if it is a passage.
+    pub fn parent(&self) -> Option<DocumentId> {
+        self.parent
+    }
+
+    /// Returns the byte range of the passage in the contents of its parent
+    /// document, if the entry is a passage.
+    pub fn range(&self) -> Option<Range<usize>> {
+        self.range.clone()
+    }
+
+    /// Returns the line of the file that the passage starts on, counted
+    /// from 0, if the entry is a passage of plain text or source code.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Returns the entries of the passages of the document, in order, if it
+    /// is split into passages.
+    pub fn passages(&self) -> impl Iterator<Item = &CollectionEntry> {
+        self.passages.iter()
+    }
+
+    /// Returns the entries of the records and the passages of the document,
+    /// which are indexed instead of the document itself.
+    pub fn children(&self) -> impl Iterator<Item = &CollectionEntry> {
+        self.records().chain(self.passages())
+    }
+
+    /// Returns the metadata fields of the document.
+    pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+        &self.fields
+    }
+
+    /// Returns the value of the metadata field with the specified name.
+    pub fn field(&self, name: &str) -> Option<&FieldValue> {
+        self.fields.get(name)
+    }
+
+    /// Returns the language that the document is written in, if it was
+    /// detected.
+    pub fn language(&self) -> Option<Language> {
+        Language::from_code(self.field("language")?.as_text()?)
+    }
+
+    /// Sets the metadata fields of the document.
+    pub fn set_fields(&mut self, fields: BTreeMap<String, FieldValue>) {
+        self.fields = fields;
+    }
+
+    /// Returns the sections of the document, in the order they appear.
+    pub fn sections(&self) -> &[Section] {
+        &self.sections
+    }
+
+    /// Sets the sections of the document.
+    pub fn set_sections(&mut self, sections: Vec<Section>) {
+        self.sections = sections;
+    }
+
+    /// Returns the section that contains the specified byte offset of the
+    /// contents of the document, i.e. the last section that starts at or
+    /// before the offset.
+    pub fn section_at(&self, offset: usize) -> Option<&Section> {
+        let idx = self.sections.partition_point(|s| s.offset() <= offset);
+        idx.checked_sub(1).map(|idx| &self.sections[idx])
+    }
+
+    /// Returns the entry of the record with the specified key, if any.
+    pub fn record(&self, key: &str) -> Option<&CollectionEntry> {
+        self.records.get(key)
+    }
+
+    /// Adds the entry of a record to the document, replacing the entry
+    /// of the record with the same key.
+    pub fn insert_record(&mut self, record: CollectionEntry) {
+        if let Some(key) = record.key() {
+            self.records.insert(key.to_string(), record);
+        }
+    }
+
+    /// Adds the entry of a passage to the document, after its other passages.
+    pub fn push_passage(&mut self, passage: CollectionEntry) {
+        self.passages.push(passage);
+    }
 }
 
 impl Ord for CollectionEntry {
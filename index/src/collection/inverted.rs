@@ -5,11 +5,15 @@ use anyhow::Context;
 
 use documents::DocumentId;
 
-use crate::collection::Collection;
+use crate::collection::{Collection, CollectionEntry};
 
-/// A structure that maps document IDs to their path.
+/// A structure that maps document IDs to their path and collection entry.
+/// The records of files that hold many records are mapped to the path of
+/// the file and the entry of the record, and so are the passages of
+/// documents to the path of the document and the entry of the passage.
+/// Passages have the metadata fields and the sections of their document.
 pub struct InvertedCollection {
-    inner: HashMap<DocumentId, PathBuf>,
+    inner: HashMap<DocumentId, (PathBuf, CollectionEntry)>,
 }
 
 impl InvertedCollection {
@@ -17,16 +21,37 @@ impl InvertedCollection {
     pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
         let collection =
             Collection::from_file(path).context("Failed to load collection from file.")?;
-        let inv = collection
-            .into_iter()
-            .map(|(path, entry)| (entry.document_id(), path.clone()))
-            .collect::<HashMap<DocumentId, PathBuf>>();
+        let mut inv = HashMap::new();
+        for (path, entry) in collection {
+            let children = entry.children().cloned().collect::<Vec<_>>();
+            if children.is_empty() {
+                inv.insert(entry.document_id(), (path, entry));
+            } else {
+                for mut child in children {
+                    if child.parent().is_some() {
+                        child.set_fields(entry.fields().clone());
+                        child.set_sections(entry.sections().to_vec());
+                    }
+                    inv.insert(child.document_id(), (path.clone(), child));
+                }
+            }
+        }
 
         Ok(InvertedCollection { inner: inv })
     }
 
     /// Returns the path of the document with specified document ID.
     pub fn get_path(&self, doc_id: DocumentId) -> Option<&PathBuf> {
-        self.inner.get(&doc_id)
+        self.inner.get(&doc_id).map(|(path, _)| path)
+    }
+
+    /// Returns the collection entry of the document with specified document ID.
+    pub fn get_entry(&self, doc_id: DocumentId) -> Option<&CollectionEntry> {
+        self.inner.get(&doc_id).map(|(_, entry)| entry)
+    }
+
+    /// Returns an iterator over the document IDs in the collection.
+    pub fn doc_ids(&self) -> impl Iterator<Item = DocumentId> + '_ {
+        self.inner.keys().copied()
     }
 }
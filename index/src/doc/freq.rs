@@ -3,18 +3,19 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use documents::DocumentId;
-use tokenize::Token;
+use tokenize::TokenId;
 
 /// A struct representing an in-memory document frequency index.
 ///
 /// This struct is used to store the postings of a single document.
-/// Each term in the document is associated with a frequency, which
-/// is the number of times the term appears in the document.
+/// Each term in the document, by its token ID, is associated with a
+/// frequency, which is the number of times the term appears in the
+/// document.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct DocumentFrequencyIndex {
     id: DocumentId,
     n_terms: u32,
-    index: HashMap<Token, u32>,
+    index: HashMap<TokenId, u32>,
 }
 
 impl DocumentFrequencyIndex {
@@ -30,22 +31,28 @@ impl DocumentFrequencyIndex {
     ///
     /// If the token is already in the index, the frequency count is
     /// incremented by one. Otherwise, a new posting is created.
-    fn add_token(&mut self, token: Token) {
+    fn add_token(&mut self, token: TokenId) {
         self.n_terms += 1;
-        if let Some(posting) = self.index.get_mut(&token) {
-            *posting += 1
-        } else {
-            self.index.insert(token, 1);
-        }
+        *self.index.entry(token).or_insert(0) += 1;
     }
 
     /// Indexes an iterator of tokens.
-    pub fn index_tokens(&mut self, tokens: impl IntoIterator<Item = Token>) {
+    pub fn index_tokens(&mut self, tokens: impl IntoIterator<Item = TokenId>) {
         for token in tokens {
             self.add_token(token);
         }
     }
 
+    /// Indexes an iterator of tokens without counting them in the total
+    /// number of terms of the document, e.g. the tokens of a second term
+    /// stream of the document, so that the terms of the document are
+    /// scored as if it only had its first stream.
+    pub fn index_extra_tokens(&mut self, tokens: impl IntoIterator<Item = TokenId>) {
+        for token in tokens {
+            *self.index.entry(token).or_insert(0) += 1;
+        }
+    }
+
     /// Returns the ID of the document that the document index is
     /// referring to.
     pub fn doc_id(&self) -> DocumentId {
@@ -58,14 +65,14 @@ impl DocumentFrequencyIndex {
     }
 
     /// Returns the number of occurrences of a term in the document.
-    pub fn term_count(&self, term: Token) -> u32 {
+    pub fn term_count(&self, term: TokenId) -> u32 {
         *self.index.get(&term).unwrap_or(&0)
     }
 }
 
 impl IntoIterator for DocumentFrequencyIndex {
-    type Item = (Token, u32);
-    type IntoIter = std::collections::hash_map::IntoIter<Token, u32>;
+    type Item = (TokenId, u32);
+    type IntoIter = std::collections::hash_map::IntoIter<TokenId, u32>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.index.into_iter()
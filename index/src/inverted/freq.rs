@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use serde_json;
 
 use documents::DocumentId;
-use tokenize::Token;
+use tokenize::TokenId;
 
 use crate::doc::freq::DocumentFrequencyIndex;
 use crate::doc::term::DocumentTermsCounter;
@@ -19,10 +19,10 @@ const SIGNATURE: &[u8] = b"SEARHINE";
 const VERSION: u8 = 1;
 
 /// An in-memory inverted index. The inverted index is a HashMap with
-/// the token as the key and a postings list as the value.
+/// the token ID as the key and a postings list as the value.
 #[derive(Default, Debug, Serialize, Deserialize)]
 struct FrequencyInvertedIndex {
-    inner: HashMap<Token, FrequencyPostingsList>,
+    inner: HashMap<TokenId, FrequencyPostingsList>,
 }
 
 impl FrequencyInvertedIndex {
@@ -112,6 +112,15 @@ impl FrequencyIndex {
         self.inverted_index.index(doc_index);
     }
 
+    /// Returns the token ID of each term in the index, with the number of
+    /// documents that contain it.
+    pub fn doc_frequencies(&self) -> impl Iterator<Item = (TokenId, u32)> + '_ {
+        self.inverted_index
+            .inner
+            .iter()
+            .map(|(&term, postings_list)| (term, postings_list.len() as u32))
+    }
+
     /// Writes inverted index with frequency postings to file.
     pub fn into_file(self, path: impl AsRef<Path>) -> anyhow::Result<()> {
         let path = path.as_ref();
@@ -145,18 +154,18 @@ impl Index for FrequencyIndex {
         self.doc_terms_counter.n_terms_total()
     }
 
-    fn doc_ids_containing(&self, term: &Token) -> Vec<DocumentId> {
-        let res = self.inverted_index.inner.get(term);
+    fn doc_ids_containing(&self, term: TokenId) -> Vec<DocumentId> {
+        let res = self.inverted_index.inner.get(&term);
         match res {
             Some(postings_list) => postings_list.doc_ids(),
             _ => Vec::new(),
         }
     }
 
-    fn n_docs_containing(&self, term: &Token) -> u32 {
+    fn n_docs_containing(&self, term: TokenId) -> u32 {
         self.inverted_index
             .inner
-            .get(term)
+            .get(&term)
             .map_or(0, |p_lst| p_lst.len() as u32)
     }
 
@@ -164,8 +173,8 @@ impl Index for FrequencyIndex {
         self.doc_terms_counter.n_terms(doc_id)
     }
 
-    fn term_frequency(&self, doc_id: DocumentId, term: &Token) -> u32 {
-        let x = self.inverted_index.inner.get(term).unwrap();
+    fn term_frequency(&self, doc_id: DocumentId, term: TokenId) -> u32 {
+        let x = self.inverted_index.inner.get(&term).unwrap();
         let y = x.get(doc_id).unwrap();
         y.frequency()
     }
@@ -173,25 +182,28 @@ impl Index for FrequencyIndex {
 
 #[cfg(test)]
 mod tests {
-    // #[test]
-    // fn test_frequency_indexing() {
-    //     let tokens_1 = vec![1, 2, 3, 1, 4];
-    //     let mut doc_index_1 = DocumentFrequencyIndex::new(0);
-    //     doc_index_1.index_tokens(tokens_1);
-    //
-    //     let tokens_2 = vec![1, 2, 5];
-    //     let mut doc_index_2 = DocumentFrequencyIndex::new(1);
-    //     doc_index_2.index_tokens(tokens_2);
-    //
-    //     let mut index = FrequencyIndex::new();
-    //     index.index(doc_index_1);
-    //     index.index(doc_index_2);
-    //
-    //     assert_eq!(index.n_docs(), 2);
-    //     assert_eq!(index.n_docs_containing(1), 2);
-    //     assert_eq!(index.n_terms(0), 5);
-    //     assert_eq!(index.n_terms(1), 3);
-    //     assert_eq!(index.term_frequency(0, 1), 2);
-    //     assert_eq!(index.term_frequency(1, 1), 1);
-    // }
+    use super::*;
+
+    #[test]
+    fn test_frequency_indexing() {
+        let tokens_1 = vec![1, 2, 3, 1, 4];
+        let mut doc_index_1 = DocumentFrequencyIndex::new(0);
+        doc_index_1.index_tokens(tokens_1);
+
+        let tokens_2 = vec![1, 2, 5];
+        let mut doc_index_2 = DocumentFrequencyIndex::new(1);
+        doc_index_2.index_tokens(tokens_2);
+
+        let mut index = FrequencyIndex::new();
+        index.index(doc_index_1);
+        index.index(doc_index_2);
+
+        assert_eq!(index.n_docs(), 2);
+        assert_eq!(index.n_docs_containing(1), 2);
+        assert_eq!(index.n_terms(0), 5);
+        assert_eq!(index.n_terms(1), 3);
+        assert_eq!(index.term_frequency(0, 1), 2);
+        assert_eq!(index.term_frequency(1, 1), 1);
+        assert_eq!(index.doc_ids_containing(5), vec![1]);
+    }
 }
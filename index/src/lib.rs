@@ -3,3 +3,4 @@ pub mod doc;
 pub mod inverted;
 pub mod postings;
 pub mod score;
+pub mod store;
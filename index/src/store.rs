@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use documents::DocumentId;
+
+const SIGNATURE: &[u8] = b"SRCHSTOR";
+const VERSION: u8 = 1;
+
+/// The number of bytes of text that are gathered in a block before it is
+/// compressed.
+const BLOCK_LEN: usize = 64 * 1024;
+
+/// The compression level of the blocks.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// The location of a compressed block in the store file.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Block {
+    offset: u64,
+    len: u64,
+}
+
+/// The location of the text of a document, as the index of its block and
+/// its byte range in the decompressed block.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct Slot {
+    block: usize,
+    start: usize,
+    end: usize,
+}
+
+/// The table of contents of the store, which is written at its end.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Table {
+    blocks: Vec<Block>,
+    slots: BTreeMap<DocumentId, Slot>,
+}
+
+/// A writer of a document store, which keeps the extracted text of the
+/// indexed documents.
+///
+/// The texts are gathered in blocks, which are compressed with zstd and
+/// written one after another, after a header with the signature and the
+/// version of the store. When the writer is finished, the table of the
+/// blocks and of the location of each document's text is written, and
+/// the last 8 bytes of the file are the offset of the table.
+pub struct DocumentStoreWriter {
+    writer: BufWriter<File>,
+    offset: u64,
+    block: Vec<u8>,
+    table: Table,
+}
+
+impl DocumentStoreWriter {
+    /// Creates a new document store at the specified path, replacing the
+    /// store that is there.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(SIGNATURE)?;
+        writer.write_all(&[VERSION])?;
+        Ok(Self {
+            writer,
+            offset: (SIGNATURE.len() + 1) as u64,
+            block: Vec::with_capacity(BLOCK_LEN),
+            table: Table::default(),
+        })
+    }
+
+    /// Adds the text of the document with the specified ID, replacing the
+    /// text that was added for it before.
+    pub fn add(&mut self, doc_id: DocumentId, text: &str) -> io::Result<()> {
+        let start = self.block.len();
+        self.block.extend_from_slice(text.as_bytes());
+        let slot = Slot {
+            block: self.table.blocks.len(),
+            start,
+            end: self.block.len(),
+        };
+        self.table.slots.insert(doc_id, slot);
+        if self.block.len() >= BLOCK_LEN {
+            self.flush_block()?;
+        }
+        Ok(())
+    }
+
+    /// Compresses the current block, and writes it to the file.
+    fn flush_block(&mut self) -> io::Result<()> {
+        if self.block.is_empty() {
+            return Ok(());
+        }
+        let compressed = zstd::bulk::compress(&self.block, COMPRESSION_LEVEL)?;
+        self.writer.write_all(&compressed)?;
+        self.table.blocks.push(Block {
+            offset: self.offset,
+            len: compressed.len() as u64,
+        });
+        self.offset += compressed.len() as u64;
+        self.block.clear();
+        Ok(())
+    }
+
+    /// Writes the last block and the table of the store, and closes it.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.flush_block()?;
+        serde_json::to_writer(&mut self.writer, &self.table)?;
+        self.writer.write_all(&self.offset.to_le_bytes())?;
+        self.writer.flush()
+    }
+}
+
+/// A reader of a document store, which looks up the text of the indexed
+/// documents by their IDs. The last block that is read is kept in memory,
+/// so that the texts of documents that were indexed together are read
+/// quickly.
+pub struct DocumentStore {
+    reader: BufReader<File>,
+    table: Table,
+    cache: Option<(usize, Vec<u8>)>,
+}
+
+impl DocumentStore {
+    /// Opens the document store at the specified path.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut header = [0; SIGNATURE.len() + 1];
+        reader.read_exact(&mut header)?;
+        if &header[..SIGNATURE.len()] != SIGNATURE || header[SIGNATURE.len()] != VERSION {
+            return Err(invalid_data("not a document store of this version"));
+        }
+        let mut offset = [0; 8];
+        reader.seek(SeekFrom::End(-8))?;
+        reader.read_exact(&mut offset)?;
+        let offset = u64::from_le_bytes(offset);
+        let end = reader.seek(SeekFrom::End(-8))?;
+        let table_len = end
+            .checked_sub(offset)
+            .ok_or_else(|| invalid_data("invalid offset of the table"))?;
+        reader.seek(SeekFrom::Start(offset))?;
+        let table = serde_json::from_reader(reader.by_ref().take(table_len))?;
+        Ok(Self {
+            reader,
+            table,
+            cache: None,
+        })
+    }
+
+    /// Returns true if the store has the text of the document with the
+    /// specified ID.
+    pub fn contains(&self, doc_id: DocumentId) -> bool {
+        self.table.slots.contains_key(&doc_id)
+    }
+
+    /// Returns the number of documents in the store.
+    pub fn len(&self) -> usize {
+        self.table.slots.len()
+    }
+
+    /// Returns true if the store has no documents.
+    pub fn is_empty(&self) -> bool {
+        self.table.slots.is_empty()
+    }
+
+    /// Returns the text of the document with the specified ID, as it was
+    /// indexed. If the store does not have the document, `None` is returned.
+    pub fn get(&mut self, doc_id: DocumentId) -> io::Result<Option<String>> {
+        let Some(slot) = self.table.slots.get(&doc_id).copied() else {
+            return Ok(None);
+        };
+        let block = self.read_block(slot.block)?;
+        let text = block
+            .get(slot.start..slot.end)
+            .ok_or_else(|| invalid_data("invalid range of a document"))?;
+        let text = String::from_utf8(text.to_vec()).map_err(invalid_data)?;
+        Ok(Some(text))
+    }
+
+    /// Reads and decompresses the block with the specified index, unless
+    /// it is the block that was read last.
+    fn read_block(&mut self, idx: usize) -> io::Result<&[u8]> {
+        if !matches!(&self.cache, Some((cached, _)) if *cached == idx) {
+            let block = *self
+                .table
+                .blocks
+                .get(idx)
+                .ok_or_else(|| invalid_data("invalid block of a document"))?;
+            self.reader.seek(SeekFrom::Start(block.offset))?;
+            let mut compressed = Vec::new();
+            self.reader
+                .by_ref()
+                .take(block.len)
+                .read_to_end(&mut compressed)?;
+            let data = zstd::stream::decode_all(compressed.as_slice())?;
+            self.cache = Some((idx, data));
+        }
+        Ok(self
+            .cache
+            .as_ref()
+            .map(|(_, data)| data.as_slice())
+            .unwrap())
+    }
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_store() {
+        let path = std::env::temp_dir().join(format!("searchine-store-{}", std::process::id()));
+        let mut writer = DocumentStoreWriter::create(&path).unwrap();
+        let long_text = "Lorem ipsum dolor sit amet. ".repeat(5000);
+        writer.add(0, "First document.").unwrap();
+        writer.add(1, &long_text).unwrap();
+        writer.add(7, "Ünïcode document.").unwrap();
+        writer.finish().unwrap();
+
+        let mut store = DocumentStore::open(&path).unwrap();
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.get(7).unwrap().as_deref(), Some("Ünïcode document."));
+        assert_eq!(store.get(0).unwrap().as_deref(), Some("First document."));
+        assert_eq!(store.get(1).unwrap(), Some(long_text));
+        assert_eq!(store.get(2).unwrap(), None);
+        assert!(!store.contains(2));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+/// Separates the exact words of a query from its free text. An exact word
+/// is written as `=word`, and matches the word exactly as it is written in
+/// the documents, with its case, and without stemming.
+///
+/// Returns the free text without the exact words, and the exact words
+/// without their `=`.
+///
+/// # Examples
+///
+/// ```
+/// use query::split_exact;
+///
+/// let (text, exact) = split_exact("the =Rust book");
+/// assert_eq!(text, "the book");
+/// assert_eq!(exact, ["Rust"]);
+/// ```
+pub fn split_exact(query: &str) -> (String, Vec<String>) {
+    let mut words = Vec::new();
+    let mut exact = Vec::new();
+    for word in query.split_whitespace() {
+        match word.strip_prefix('=') {
+            Some(word) if !word.is_empty() => exact.push(word.to_string()),
+            _ => words.push(word),
+        }
+    }
+    (words.join(" "), exact)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_exact() {
+        let (text, exact) = split_exact("=C++ and =node.js = runtime");
+        assert_eq!(text, "and = runtime");
+        assert_eq!(exact, ["C++", "node.js"]);
+        let (text, exact) = split_exact("ownership");
+        assert_eq!(text, "ownership");
+        assert!(exact.is_empty());
+    }
+}
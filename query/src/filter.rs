@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use documents::{Date, FieldValue};
+
+/// The comparison that a filter applies to a field value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl FilterOp {
+    /// Returns true if the ordering of a field value relative to the
+    /// filter value satisfies the comparison.
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            Self::Eq => ordering.is_eq(),
+            Self::Lt => ordering.is_lt(),
+            Self::Le => ordering.is_le(),
+            Self::Gt => ordering.is_gt(),
+            Self::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+/// A filter on a metadata field of the documents, written in a query
+/// as `field:value`, or `field:<op>value` with one of the comparison
+/// operators `=`, `<`, `<=`, `>` and `>=`.
+///
+/// For example, `tags:rust` matches the documents tagged with `rust`,
+/// and `date:>=2024-01-01` the documents dated from 2024 onwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    field: String,
+    op: FilterOp,
+    value: String,
+}
+
+impl Filter {
+    /// Parses a filter from a query word. If the word is not a filter,
+    /// `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use query::{Filter, FilterOp};
+    ///
+    /// let filter = Filter::parse("date:>=2024-01-01").unwrap();
+    /// assert_eq!(filter.field(), "date");
+    /// assert_eq!(filter.op(), FilterOp::Ge);
+    /// assert_eq!(filter.value(), "2024-01-01");
+    /// assert!(Filter::parse("rust").is_none());
+    /// ```
+    pub fn parse(word: &str) -> Option<Self> {
+        let (field, value) = word.split_once(':')?;
+        let is_name = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+        if field.is_empty() || !field.chars().all(is_name) {
+            return None;
+        }
+        let (op, value) = [
+            (">=", FilterOp::Ge),
+            ("<=", FilterOp::Le),
+            (">", FilterOp::Gt),
+            ("<", FilterOp::Lt),
+            ("=", FilterOp::Eq),
+        ]
+        .into_iter()
+        .find_map(|(prefix, op)| Some((op, value.strip_prefix(prefix)?)))
+        .unwrap_or((FilterOp::Eq, value));
+        if value.is_empty() || value.starts_with('/') {
+            return None;
+        }
+        Some(Self {
+            field: field.to_lowercase(),
+            op,
+            value: value.to_string(),
+        })
+    }
+
+    /// Returns the name of the filtered field.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the comparison of the filter.
+    pub fn op(&self) -> FilterOp {
+        self.op
+    }
+
+    /// Returns the value that the field is compared to.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns true if a field value satisfies the filter.
+    ///
+    /// Text is compared case-insensitively, and a list satisfies the
+    /// filter if any of its items does. Numbers, booleans and dates
+    /// are compared by value, so the filter value must parse as the
+    /// same type.
+    pub fn matches(&self, value: &FieldValue) -> bool {
+        match value {
+            FieldValue::Text(text) => self.matches_text(text),
+            FieldValue::List(items) => items.iter().any(|item| self.matches_text(item)),
+            FieldValue::Number(number) => self
+                .value
+                .parse::<f64>()
+                .ok()
+                .and_then(|value| number.partial_cmp(&value))
+                .is_some_and(|ordering| self.op.accepts(ordering)),
+            FieldValue::Bool(bool) => self
+                .value
+                .parse::<bool>()
+                .is_ok_and(|value| self.op.accepts(bool.cmp(&value))),
+            FieldValue::Date(date) => self
+                .value
+                .parse::<Date>()
+                .is_ok_and(|value| self.op.accepts(date.cmp(&value))),
+        }
+    }
+
+    /// Returns true if the filter holds for a set of fields. A filter
+    /// on a missing field does not hold.
+    pub fn matches_fields(&self, fields: &BTreeMap<String, FieldValue>) -> bool {
+        fields
+            .get(&self.field)
+            .is_some_and(|value| self.matches(value))
+    }
+
+    fn matches_text(&self, text: &str) -> bool {
+        let ordering = text.to_lowercase().cmp(&self.value.to_lowercase());
+        self.op.accepts(ordering)
+    }
+}
+
+/// Splits a query into its free text and its field filters.
+///
+/// # Examples
+///
+/// ```
+/// use query::split_filters;
+///
+/// let (text, filters) = split_filters("parse config tags:rust");
+/// assert_eq!(text, "parse config");
+/// assert_eq!(filters.len(), 1);
+/// ```
+pub fn split_filters(query: &str) -> (String, Vec<Filter>) {
+    let mut words = Vec::new();
+    let mut filters = Vec::new();
+    for word in query.split_whitespace() {
+        match Filter::parse(word) {
+            Some(filter) => filters.push(filter),
+            None => words.push(word),
+        }
+    }
+    (words.join(" "), filters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_matches() {
+        let tags = FieldValue::List(vec!["Rust".to_string(), "search".to_string()]);
+        assert!(Filter::parse("tags:rust").unwrap().matches(&tags));
+        assert!(!Filter::parse("tags:python").unwrap().matches(&tags));
+
+        let date = FieldValue::Date("2024-03-01".parse().unwrap());
+        assert!(Filter::parse("date:>=2024-01-01").unwrap().matches(&date));
+        assert!(!Filter::parse("date:<2024-01-01").unwrap().matches(&date));
+        assert!(!Filter::parse("date:>=soon").unwrap().matches(&date));
+
+        let pages = FieldValue::Number(120.0);
+        assert!(Filter::parse("pages:>100").unwrap().matches(&pages));
+    }
+
+    #[test]
+    fn test_split_filters() {
+        let (text, filters) = split_filters("https://example.com author:ada notes");
+        assert_eq!(text, "https://example.com notes");
+        assert_eq!(filters, vec![Filter::parse("author:ada").unwrap()]);
+    }
+}
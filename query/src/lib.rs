@@ -1,24 +1,32 @@
 use std::collections::HashMap;
 
-use tokenize::Token;
+use tokenize::TokenId;
 
-/// Structure that stores the token and its count. /// /// For example, after we have tokenized a query, we can
+pub use exact::split_exact;
+pub use filter::{split_filters, Filter, FilterOp};
+
+pub mod exact;
+pub mod filter;
+
+/// Structure that stores the token ID and its count.
+///
+/// For example, after we have tokenized and encoded a query, we can
 /// create a token counter for more efficient relevance scoring.
 #[derive(Debug)]
 pub struct Query {
-    inner: HashMap<Token, u32>,
+    inner: HashMap<TokenId, u32>,
 }
 
 impl Query {
     /// Returns the count of a specific token.
-    pub fn term_count(&self, token: &Token) -> u32 {
-        *self.inner.get(token).unwrap_or(&0)
+    pub fn term_count(&self, token: TokenId) -> u32 {
+        *self.inner.get(&token).unwrap_or(&0)
     }
 }
 
 impl<I> From<I> for Query
 where
-    I: IntoIterator<Item=Token>,
+    I: IntoIterator<Item=TokenId>,
 {
     /// Creates token counter from a stream of tokens.
     fn from(tokens: I) -> Self {
@@ -33,8 +41,8 @@ where
 }
 
 impl IntoIterator for Query {
-    type Item = (Token, u32);
-    type IntoIter = std::collections::hash_map::IntoIter<Token, u32>;
+    type Item = (TokenId, u32);
+    type IntoIter = std::collections::hash_map::IntoIter<TokenId, u32>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.inner.into_iter()
@@ -43,7 +51,7 @@ impl IntoIterator for Query {
 
 #[cfg(test)]
 mod tests {
-    use tokenize::Tokenizer;
+    use tokenize::{Encoder, Tokenizer};
 
     use super::*;
 
@@ -51,12 +59,13 @@ mod tests {
     fn test_construction() {
         let text = "I want it. I got it.".to_string();
         let mut tokenizer = Tokenizer::default();
+        let mut encoder = Encoder::new();
         let tokens = tokenizer.tokenize(text);
-        let token_counts = Query::from(tokens);
-        assert_eq!(token_counts.term_count(&"i".to_string()), 2);
-        assert_eq!(token_counts.term_count(&"want".to_string()), 1);
-        assert_eq!(token_counts.term_count(&"it".to_string()), 2);
-        assert_eq!(token_counts.term_count(&"got".to_string()), 1);
-        assert_eq!(token_counts.term_count(&"ai".to_string()), 0);
+        let token_counts = Query::from(tokens.iter().map(|token| encoder.encode(token.term())));
+        assert_eq!(token_counts.term_count(encoder.encode("i")), 2);
+        assert_eq!(token_counts.term_count(encoder.encode("want")), 1);
+        assert_eq!(token_counts.term_count(encoder.encode("it")), 2);
+        assert_eq!(token_counts.term_count(encoder.encode("got")), 1);
+        assert_eq!(token_counts.term_count(encoder.encode("ai")), 0);
     }
 }
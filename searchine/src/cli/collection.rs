@@ -5,6 +5,7 @@ use std::path::Path;
 use anyhow::Context;
 use tabwriter::TabWriter;
 
+use documents::{Date, FieldValue};
 use index::collection::*;
 
 use crate::config::COLLECTION_FILENAME;
@@ -43,7 +44,9 @@ pub fn index(repo_dir: impl AsRef<Path>, verbose: bool) -> anyhow::Result<()> {
 /// Lists the indexed documents in the corpus, which are listed in the
 /// corpus-index. The result is printed to the standard output as a table.
 /// The first column is the path to the document, the second column is the
-/// document ID, and the third column is the last modified time.
+/// document ID, and the last column is the last modified date. The columns
+/// in between show the metadata of the documents that have been indexed:
+/// their title, author, MIME type, language, word count and size.
 ///
 /// # Arguments
 ///
@@ -63,14 +66,31 @@ pub fn list(repo_dir: impl AsRef<Path>) -> io::Result<()> {
     let emoji = String::from_utf8(vec![0xF0, 0x9F, 0x93, 0x9A]).unwrap_or_default();
     println!("{emoji} Documents in the corpus: {}\n", corpus_index.len());
     let mut tab_writer = TabWriter::new(io::stdout());
-    _ = writeln!(tab_writer, "\tPath\tDocument ID\tLast Modified");
+    _ = writeln!(
+        tab_writer,
+        "\tPath\tDocument ID\tTitle\tAuthor\tType\tLanguage\tWords\tSize\tLast Modified"
+    );
     for (path, entry) in corpus_index {
+        let field = |name| {
+            entry
+                .field(name)
+                .map_or("-".to_string(), FieldValue::to_string)
+        };
+        let modified = entry
+            .field("modified")
+            .and_then(FieldValue::as_date)
+            .unwrap_or_else(|| Date::from(entry.modified()));
         _ = writeln!(
             tab_writer,
-            "\t{}\t{}\t{:?}",
+            "\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{modified}",
             path.display(),
             entry.document_id(),
-            entry.modified()
+            field("title"),
+            field("author"),
+            field("mime"),
+            field("language"),
+            field("words"),
+            field("size"),
         );
     }
     tab_writer.flush()
@@ -1,67 +1,231 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::mpsc::{Receiver, sync_channel};
+use std::sync::mpsc::{Receiver, SyncSender, sync_channel};
 use std::thread::{JoinHandle, spawn};
 
 use anyhow::Context;
 
-use documents::{Document, DocumentId};
+use documents::read::archive::{self, MemberPath};
+use documents::split::{SplitOptions, TextSplit};
+use documents::{Document, DocumentId, DocumentType, ReadOptions};
 use index::collection::*;
 use index::doc::freq::DocumentFrequencyIndex;
 use index::inverted::freq::FrequencyIndex;
-use tokenize::{Token, Tokenizer};
+use index::store::DocumentStoreWriter;
+use tokenize::{exact_token, field_token, Encoder, LanguageOptions, Term, Token, Tokenizer};
 
-use crate::config::{CHANNEL_BOUND, INDEX_FILENAME};
+use crate::config::{
+    IndexSettings, RepoConfig, CHANNEL_BOUND, COLLECTION_FILENAME, INDEX_FILENAME,
+    STORE_FILENAME, VOCABULARY_FILENAME,
+};
 use crate::fs::Directory;
 
-type TokenizedDocument = (DocumentId, Vec<Token>);
+/// The ID of a document, its filtered terms, and its exact terms.
+type TokenizedDocument = (DocumentId, Vec<Term>, Vec<Term>);
+
+/// The path of a document that failed to load, and the reason it failed.
+type LoadFailure = (PathBuf, anyhow::Error);
+
+/// The collection of the loaded documents, and the documents that failed to load.
+type LoadedCollection = (Collection, Vec<LoadFailure>);
 
 /// Part of a pipeline that loads documents. Each document in the collection is loaded
-/// and sent over a channel for tokenizing.
+/// and sent over a channel for tokenizing. Files that hold many records, e.g. JSONL
+/// files or TREC collections, are streamed as a document per record, and each record
+/// is added to the collection with its own document ID and its key in the file. The
+/// metadata fields of each document are stored in the collection, which is returned
+/// when all documents are loaded.
+///
+/// Documents that fail to load, e.g. binary files with the extension of a text file,
+/// are not indexed, and are returned with the reason that they failed.
+///
+/// If split options are given, each document is split into passages, which are added
+/// to the collection with their own document IDs, and are sent instead of the document.
+///
+/// The members of an archive that follow each other in the paths are loaded in the
+/// order they are stored in the archive, in one pass over it.
 fn load_docs<I>(
     paths: I,
-    collection: Collection,
-) -> (Receiver<Document>, JoinHandle<anyhow::Result<()>>)
+    mut collection: Collection,
+    options: ReadOptions,
+    passages: Option<SplitOptions>,
+) -> (Receiver<Document>, JoinHandle<anyhow::Result<LoadedCollection>>)
 where
     I: IntoIterator<Item=PathBuf> + Send + 'static,
 {
     let (sender, receiver) = sync_channel(CHANNEL_BOUND);
     let handle = spawn(move || {
-        for path in paths {
-            // For each path, we map it to a document id with the collection index,
-            // and load the document from file.
-            let doc_id = collection
-                .get_document_id(&path)
-                .context(format!("Document {} is not in collection.", path.display()))?;
-            // println!("Loading document: {}", path.display());
-            let document = Document::from_file(doc_id, &path)?;
+        let splitter = passages.map(|passages| passages.splitter());
+        let splitter = splitter.as_deref();
+        let mut failures = Vec::new();
+        let mut load =
+            |path: &PathBuf| load_doc(path, &mut collection, &options, splitter, &sender);
+        let mut paths = paths.into_iter().peekable();
+        while let Some(path) = paths.next() {
+            let Some(member_path) = MemberPath::parse(&path) else {
+                if let Err(err) = load(&path) {
+                    failures.push((path, err));
+                }
+                continue;
+            };
+            let archive = member_path.archive().to_path_buf();
+            let mut members = BTreeMap::from([(member_path.member().to_string(), path)]);
+            while let Some(member_path) = paths
+                .peek()
+                .and_then(MemberPath::parse)
+                .filter(|member_path| member_path.archive() == archive)
+            {
+                members.insert(member_path.member().to_string(), paths.next().unwrap());
+            }
+            let names = members.keys().cloned().collect::<HashSet<_>>();
+            let streamed = archive::stream_members(&archive, &names, |member_path| {
+                let path = members.remove(member_path.member()).unwrap();
+                if let Err(err) = load(&path) {
+                    failures.push((path, err));
+                }
+            });
+            // Members that were not streamed are not in the archive, or the archive
+            // failed to read, and fail to load with the reason.
+            for path in members.into_values() {
+                let loaded = match &streamed {
+                    Ok(()) => load(&path),
+                    Err(err) => Err(anyhow::anyhow!("Failed to read archive: {err}")),
+                };
+                if let Err(err) = loaded {
+                    failures.push((path, err));
+                }
+            }
+        }
+        Ok((collection, failures))
+    });
+    (receiver, handle)
+}
 
+/// Loads the document at the specified path, or its records or passages, and sends
+/// them over the channel.
+fn load_doc(
+    path: &PathBuf,
+    collection: &mut Collection,
+    options: &ReadOptions,
+    splitter: Option<&dyn TextSplit>,
+    sender: &SyncSender<Document>,
+) -> anyhow::Result<()> {
+    // Each path is mapped to a document id with the collection index,
+    // and the document is loaded from file.
+    let doc_id = collection
+        .get_document_id(path)
+        .context(format!("Document {} is not in collection.", path.display()))?;
+    let document_type = DocumentType::detect(path)
+        .context(format!("Failed to read file {}", path.display()))?;
+    if let Some(reader) = document_type.and_then(|dt| options.record_reader(dt)) {
+        let records = reader
+            .records(path)
+            .context(format!("Failed to read records from {}", path.display()))?;
+        for record in records {
+            let record =
+                record.context(format!("Failed to read record from {}", path.display()))?;
+            let key = record.key().to_string();
+            let Some(doc_id) = collection.insert_record(path, &key, record.fields().clone())
+            else {
+                eprintln!("Skipping duplicate record {key} in {}", path.display());
+                continue;
+            };
+            let document = Document::from_record(doc_id, path, record);
             if sender.send(document).is_err() {
-                eprintln!("Failed to read from {}", path.display());
+                eprintln!("Failed to read record {key} from {}", path.display());
             }
         }
+        return Ok(());
+    }
+    let document = Document::from_file_with_options(doc_id, path, options)?;
+    collection.set_fields(path, document.metadata().fields().clone());
+    collection.set_sections(path, document.metadata().sections().to_vec());
+
+    let Some(splitter) = splitter else {
+        if sender.send(document).is_err() {
+            eprintln!("Failed to read from {}", path.display());
+        }
+        return Ok(());
+    };
+    // The text fields of the document are indexed with its first passage. Passages
+    // of contents that keep the lines of their file are given their first line.
+    let mut text_fields = document.text_fields().clone();
+    let content = document.page_content();
+    let keeps_lines = document_type.is_some_and(|dt| dt.keeps_lines());
+    for range in splitter.split_ranges(content) {
+        let line = keeps_lines.then(|| content[..range.start].matches('\n').count());
+        let Some(passage_id) = collection.insert_passage(path, range.clone(), line) else {
+            continue;
+        };
+        let mut passage = document.passage(passage_id, range);
+        for (name, text) in std::mem::take(&mut text_fields) {
+            passage = passage.with_text_field(&name, text);
+        }
+        if sender.send(passage).is_err() {
+            eprintln!("Failed to read passage {passage_id} from {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Part of a pipeline that stores the contents of the documents. If a path is given,
+/// the contents of each received document are added to a document store at that path,
+/// from which the snippets of search results are taken. The documents are forwarded
+/// over a channel for tokenizing.
+fn store_documents(
+    document_receiver: Receiver<Document>,
+    store_path: Option<PathBuf>,
+) -> (Receiver<Document>, JoinHandle<anyhow::Result<()>>) {
+    let (sender, receiver) = sync_channel(CHANNEL_BOUND);
+    let handle = spawn(move || {
+        let mut store = match store_path {
+            Some(path) => Some(
+                DocumentStoreWriter::create(&path)
+                    .context(format!("Failed to create document store {}", path.display()))?,
+            ),
+            None => None,
+        };
+        for document in document_receiver {
+            if let Some(store) = store.as_mut() {
+                store
+                    .add(document.doc_id(), document.page_content())
+                    .context(format!("Failed to store document {}", document.doc_id()))?;
+            }
+            let doc_id = document.doc_id();
+            if sender.send(document).is_err() {
+                eprintln!("Failed to store document {doc_id}");
+            }
+        }
+        if let Some(store) = store {
+            store.finish().context("Failed to write document store.")?;
+        }
         Ok(())
     });
     (receiver, handle)
 }
 
 /// Part of a pipeline that tokenizes the contents of a document. Each received
-/// document is broken into tokens. The document ID and the stream of tokens are
-/// sent over a channel to a thread that indexes the documents.
+/// document is broken into tokens with the tokenizer of the repository, which drops
+/// the stop words. The document ID and the streams of tokens of
+/// [`tokenize_document`] are sent over a channel to a thread that indexes the
+/// documents.
+///
+/// Each document is stemmed in the language that the language options pick for it, from
+/// the language that was detected for it and stored in its metadata.
 fn tokenize_content(
     document_receiver: Receiver<Document>,
+    mut tokenizer: Tokenizer,
+    language: LanguageOptions,
 ) -> (
     Receiver<TokenizedDocument>,
     JoinHandle<anyhow::Result<()>>,
 ) {
     let (sender, receiver) = sync_channel(CHANNEL_BOUND);
-    let mut tokenizer = Tokenizer::default();
     let handle = spawn(move || {
         for document in document_receiver {
-            let tokens = (
-                document.doc_id(),
-                tokenizer.tokenize(document.page_content()),
-            );
+            tokenizer.set_language(language.stem_language(document.metadata().language()));
+            let (tokens, exact_tokens) = tokenize_document(&mut tokenizer, &document);
+            let tokens = (document.doc_id(), tokens, exact_tokens);
             if sender.send(tokens).is_err() {
                 eprintln!("Failed to tokenize document {}", document.doc_id());
             }
@@ -71,27 +235,69 @@ fn tokenize_content(
     (receiver, handle)
 }
 
-/// Part of a pipeline that creates an index for each document. The received
-/// pair of document ID and token stream are turned into an index. The resulting
-/// document indices are sent over a channel and joint in the main thread that
-/// merges them into an overall index.
+/// Tokenizes the page content and the text fields of a document. Source code is
+/// tokenized by identifiers, and the tokens of the text fields of the document are
+/// prefixed by the field name. Next to the filtered terms, the exact terms are
+/// returned, with the case-preserved words that were not passed through the filters,
+/// e.g. `=Rust`, for exact matching. The stop words of the tokenizer are left out of
+/// the exact terms too, whatever their case, so that e.g. `=The` is not indexed.
+fn tokenize_document(
+    tokenizer: &mut Tokenizer,
+    document: &Document,
+) -> (Vec<Term>, Vec<Term>) {
+    let content = document.page_content();
+    let (tokens, exact_tokens) = match document.metadata().document_type() {
+        Some(DocumentType::Code(_)) => {
+            (tokenizer.tokenize_code(content), tokenizer.pre_tokenize_code(content))
+        }
+        _ => (tokenizer.tokenize(content), tokenizer.pre_tokenize(content)),
+    };
+    let is_exact = |token: &&Token| !tokenizer.is_stop_word(token.term());
+    let mut terms = tokens.into_iter().map(Token::into_term).collect::<Vec<_>>();
+    let mut exact_terms = exact_tokens
+        .iter()
+        .filter(is_exact)
+        .map(|token| exact_token(token.term()))
+        .collect::<Vec<_>>();
+    for (field, text) in document.text_fields() {
+        let field_tokens = tokenizer.tokenize(text);
+        terms.extend(field_tokens.iter().map(|token| field_token(field, token.term())));
+        let field_tokens = tokenizer.pre_tokenize(text);
+        let is_exact = |token: &&Token| !tokenizer.is_stop_word(token.term());
+        exact_terms.extend(
+            field_tokens
+                .iter()
+                .filter(is_exact)
+                .map(|token| field_token(field, &exact_token(token.term()))),
+        );
+    }
+    (terms, exact_terms)
+}
+
+/// Part of a pipeline that creates an index for each document. The terms of the
+/// received document ID and token streams are encoded to token IDs with the
+/// vocabulary of the index, and are turned into an index. The resulting document
+/// indices are sent over a channel and joint in the main thread that merges them
+/// into an overall index. The vocabulary is returned when all documents are indexed.
 fn index_documents(
-    tokens_receiver: Receiver<(u32, Vec<Token>)>,
+    tokens_receiver: Receiver<TokenizedDocument>,
 ) -> (
     Receiver<DocumentFrequencyIndex>,
-    JoinHandle<anyhow::Result<()>>,
+    JoinHandle<anyhow::Result<Encoder>>,
 ) {
     let (sender, receiver) = sync_channel(CHANNEL_BOUND);
     let handle = spawn(move || {
-        for (doc_id, tokens) in tokens_receiver {
+        let mut encoder = Encoder::new();
+        for (doc_id, tokens, exact_tokens) in tokens_receiver {
             let mut doc_index = DocumentFrequencyIndex::new(doc_id);
-            doc_index.index_tokens(tokens);
+            doc_index.index_tokens(tokens.iter().map(|token| encoder.encode(token)));
+            doc_index.index_extra_tokens(exact_tokens.iter().map(|token| encoder.encode(token)));
 
             if sender.send(doc_index).is_err() {
                 eprintln!("Failed to send index {}", doc_id)
             }
         }
-        Ok(())
+        Ok(encoder)
     });
     (receiver, handle)
 }
@@ -103,28 +309,73 @@ pub fn invoke_par(repo_dir: impl AsRef<Path>, verbose: bool) -> anyhow::Result<(
     let dir_path = repo_dir
         .parent()
         .context(format!("Failed to get parent for: {}", repo_dir.display()))?;
+    let config = RepoConfig::from_repo(repo_dir).context("Failed to load repository config.")?;
     let dir = Directory::new(dir_path)?;
     let dir = dir.iter_full_paths(verbose).collect::<BTreeSet<_>>();
 
     // This is indexing collection from the scratch?
     let collection = Collection::from_paths(dir.clone())?;
 
-    let (doc_rec, h1) = load_docs(dir, collection);
-    let (token_rec, h2) = tokenize_content(doc_rec);
-    let (doc_index_rec, h3) = index_documents(token_rec);
+    // The document store is only kept if it is enabled, so that a stale store
+    // is not used for snippets.
+    let store_path = repo_dir.join(STORE_FILENAME);
+    if !config.store && store_path.exists() {
+        std::fs::remove_file(&store_path)?;
+    }
+    let store_path = config.store.then_some(store_path);
+
+    let settings = IndexSettings::from_config(&config);
+    let tokenizer = settings.tokenizer().context("Failed to create the tokenizer.")?;
+    let (doc_rec, h1) = load_docs(dir, collection, config.read, config.passages);
+    let (stored_rec, h2) = store_documents(doc_rec, store_path);
+    let (token_rec, h3) = tokenize_content(stored_rec, tokenizer, settings.language);
+    let (doc_index_rec, h4) = index_documents(token_rec);
     let mut index = FrequencyIndex::new();
     for doc_index in doc_index_rec {
         index.index(doc_index);
     }
 
-    let _r1 = h1.join().unwrap()?;
-    let _r2 = h2.join().unwrap()?;
-    let _r3 = h3.join().unwrap()?;
+    let (collection, failures) = h1.join().unwrap()?;
+    h2.join().unwrap()?;
+    h3.join().unwrap()?;
+    let vocabulary = h4.join().unwrap()?;
 
-    // Build index and store it to file.
+    // Build index and store it to file, along with its vocabulary, the
+    // settings it was created with, and the collection and the metadata of
+    // its documents.
     index.into_file(repo_dir.join(INDEX_FILENAME))?;
+    vocabulary.into_file(repo_dir.join(VOCABULARY_FILENAME))?;
+    settings.into_repo(repo_dir)?;
+    collection.into_file(repo_dir.join(COLLECTION_FILENAME))?;
     let emoji = String::from_utf8(vec![0xF0, 0x9F, 0x93, 0x8B]).unwrap_or_default();
     println_bold!("{emoji} Created index for: {}", dir_path.display());
+    if !failures.is_empty() {
+        println_bold!("Skipped {} documents that failed to load:", failures.len());
+        for (path, err) in &failures {
+            println_red!("\t{}: {err:#}", path.display());
+        }
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use documents::core::metadata::DocumentMetadataBuilder;
+    use tokenize::TokenizerOptions;
+
+    use super::*;
+
+    #[test]
+    fn test_tokenize_document() {
+        let mut tokenizer = TokenizerOptions::default().tokenizer().unwrap();
+        let metadata = DocumentMetadataBuilder::new().build();
+        let text = "The Rust index of the documents".to_string();
+        let document = Document::new(0, text, metadata)
+            .with_text_field("title", "The Book".to_string());
+        let (terms, exact_terms) = tokenize_document(&mut tokenizer, &document);
+        assert_eq!(terms, ["rust", "index", "document", "title:book"]);
+        assert_eq!(exact_terms, ["=Rust", "=index", "=documents", "title:=Book"]);
+        assert!(!exact_terms.contains(&"=The".to_string()));
+    }
+}
@@ -1,7 +1,7 @@
 use std::io;
 use std::path::Path;
 
-use crate::config::SEARCHINE_PATH;
+use crate::config::{RepoConfig, SEARCHINE_PATH};
 
 /// Initializes a new searchine index repo.
 ///
@@ -13,6 +13,7 @@ pub fn invoke(dir_path: impl AsRef<Path>) -> io::Result<()> {
     let dir_path = dir_path.as_ref();
     let index_path = dir_path.join(SEARCHINE_PATH);
     std::fs::create_dir_all(&index_path)?;
+    RepoConfig::default().into_repo(&index_path)?;
     let full_index_path = std::fs::canonicalize(&index_path)?;
     let emoji = String::from_utf8(vec![0xF0, 0x9F, 0x93, 0x82]).unwrap_or_default();
     println_bold!("{emoji} Index created at: {}", full_index_path.display());
@@ -3,6 +3,7 @@ pub mod index;
 pub mod init;
 pub mod search;
 pub mod status;
+pub mod stop_words;
 pub mod utils;
 
 #[derive(clap::Parser)]
@@ -39,5 +40,15 @@ pub enum Commands {
         dir_path: Option<String>,
         #[clap(short, long)]
         top_n: Option<u32>,
+        /// Search a text field, e.g. `comments` or `strings` of source files.
+        #[clap(short, long)]
+        field: Option<String>,
+    },
+    /// Suggest stop words, from the terms that occur in most documents.
+    SuggestStopWords {
+        dir_path: Option<String>,
+        /// The least ratio of the documents that a term must occur in.
+        #[clap(short, long, default_value_t = 0.5)]
+        min_ratio: f64,
     },
 }
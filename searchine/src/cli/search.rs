@@ -1,57 +1,702 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 use std::io::{self, Write};
-use std::path::{Path, PathBuf};
+use std::ops::Range;
+use std::path::Path;
 
-use index::collection::InvertedCollection;
+use anyhow::Context;
+
+use documents::core::sniff::SNIFF_LEN;
+use documents::read::encoding::read_text;
+use documents::{Document, DocumentType, FieldValue, Section};
+use index::collection::{CollectionEntry, InvertedCollection};
 use index::inverted::freq::FrequencyIndex;
 use index::inverted::Index;
 use index::score::*;
-use query::Query;
-use tokenize::Tokenizer;
+use index::store::DocumentStore;
+use query::{split_exact, split_filters, Filter, Query};
+use tokenize::{
+    cjk_bigrams, exact_token, field_token, split_field_token, Encoder, Language, Term, Token,
+    Tokenizer,
+};
+
+use crate::config::{
+    IndexSettings, RepoConfig, COLLECTION_FILENAME, INDEX_FILENAME, STORE_FILENAME,
+    VOCABULARY_FILENAME,
+};
+
+/// A search result, as it is displayed.
+struct SearchResult {
+    /// The path of the document, with its key or its lines, if any.
+    location: String,
+    /// The title of the document, if known.
+    title: Option<String>,
+    /// The section of the document that matches the query, if any.
+    section: Option<String>,
+    /// The words of the stored text of the document that match the query
+    /// best, if the repository has a document store.
+    snippet: Option<String>,
+    score: f64,
+}
+
+/// The number of lines of the windows of text in which the query terms
+/// are counted, to find the lines that match the query best.
+const WINDOW_LINES: usize = 20;
+
+/// The number of words of the windows of text in which the query terms
+/// are counted, to find the snippet of a result.
+const SNIPPET_WORDS: usize = 16;
 
-use crate::config::{COLLECTION_FILENAME, INDEX_FILENAME};
+/// The number of words that are shown around the matching words of a
+/// snippet.
+const SNIPPET_CONTEXT: usize = 4;
 
 /// Search `top_n` most relevant documents to the free text `query`.
-pub fn invoke(repo_dir: impl AsRef<Path>, query: &str, top_n: u32) -> anyhow::Result<()> {
+///
+/// The query may contain filters on the metadata fields of the documents,
+/// e.g. `tags:rust` or `date:>=2024-01-01`, and only the documents that
+/// satisfy all of them are returned. If the query has no free text, all
+/// the documents that satisfy the filters are returned.
+///
+/// If a text `field` is specified, e.g. the `comments` of source files,
+/// the free text is searched in that field instead of the page contents.
+///
+/// For documents with sections, e.g. the chapters of a book, the section
+/// that matches the query best is shown with the results. Records of files
+/// that hold many records are shown as `path#key`. If the documents are
+/// split into passages, each passage is a result of its own, and is shown
+/// with the section that it starts in.
+///
+/// For text files, the lines in which the query terms occur most densely
+/// are shown with the path, e.g. `manual.md:120-138`, so that they can be
+/// opened in an editor.
+///
+/// If the repository keeps a document store, each result is shown with a
+/// snippet of its text as it was indexed, around the words that match the
+/// query best. Its section and its lines are then found in the stored text
+/// too, with the sections that are stored in the collection, so the files
+/// of the results are not read again. Lines are only found in the stored
+/// text of plain text and source code, which keeps the lines of the file.
+pub fn invoke(
+    repo_dir: impl AsRef<Path>,
+    query: &str,
+    field: Option<&str>,
+    top_n: u32,
+) -> anyhow::Result<()> {
     let repo_dir = repo_dir.as_ref();
 
-    // Instantiate tokenizer.
-    let mut tokenizer = Tokenizer::default();
-    // Tokenize the query.
-    let query_tokens = tokenizer.tokenize(query);
-    let query = Query::from(query_tokens);
-
-    // Load inverted index from file.
+    // Load inverted index, its vocabulary and collection from file.
     let index_path = repo_dir.join(INDEX_FILENAME);
     let index = FrequencyIndex::from_file(index_path)?;
+    let vocabulary_path = repo_dir.join(VOCABULARY_FILENAME);
+    let vocabulary = Encoder::from_file(&vocabulary_path).context(format!(
+        "Failed to read vocabulary from file: {}",
+        vocabulary_path.display()
+    ))?;
+    let collection_path = repo_dir.join(COLLECTION_FILENAME);
+    let inv_collection = InvertedCollection::from_file(collection_path)?;
+
+    // Instantiate the tokenizer that the index was created with, so that the
+    // query is tokenized the same way as the documents.
+    let config = RepoConfig::from_repo(repo_dir)?;
+    let settings =
+        IndexSettings::from_repo(repo_dir)?.unwrap_or_else(|| IndexSettings::from_config(&config));
+    let mut tokenizer = settings
+        .tokenizer()
+        .context("Failed to create the tokenizer.")?;
+    let stem_language = |entry: Option<&CollectionEntry>| {
+        settings.language.stem_language(entry.and_then(CollectionEntry::language))
+    };
+    // Separate the filters and the exact words, and tokenize the free text
+    // of the query, in each language that documents of the index are
+    // stemmed in.
+    let (text, filters) = split_filters(query);
+    let (text, exact_words) = split_exact(&text);
+    let languages = inv_collection
+        .doc_ids()
+        .map(|doc_id| stem_language(inv_collection.get_entry(doc_id)))
+        .collect::<HashSet<_>>();
+    let mut query_tokens = tokenize_query(&text, &mut tokenizer, &languages);
+    expand_cjk(&mut query_tokens, &vocabulary, field);
+    query_tokens.extend(tokenize_exact(&exact_words, &tokenizer));
+    let mut section_terms = query_tokens.iter().cloned().collect::<HashSet<_>>();
+    if let Some(field) = field {
+        query_tokens = query_tokens
+            .iter()
+            .map(|token| field_token(field, token))
+            .collect();
+        section_terms.clear();
+    }
+    // Terms that are not in the vocabulary are in none of the documents.
+    let query = Query::from(
+        query_tokens
+            .iter()
+            .filter_map(|token| vocabulary.get(token)),
+    );
+    let is_match = |doc_id| {
+        inv_collection.get_entry(doc_id).is_some_and(|entry| {
+            filters
+                .iter()
+                .all(|f: &Filter| f.matches_fields(entry.fields()))
+        })
+    };
 
     // Iterate over all tokens in the query and score the documents they appear in.
     let mut scores = DocumentsScores::new();
+    if text.is_empty() && exact_words.is_empty() {
+        for doc_id in inv_collection.doc_ids().filter(|&doc_id| is_match(doc_id)) {
+            scores.add_score(doc_id, 0.0);
+        }
+    }
     for (term, count) in query {
-        for doc_id in index.doc_ids_containing(&term) {
-            let score = (count as f64) * index.calc_tf_idf(doc_id, &term);
-            scores.add_score(doc_id, score);
+        for doc_id in index.doc_ids_containing(term) {
+            if is_match(doc_id) {
+                let score = (count as f64) * index.calc_tf_idf(doc_id, term);
+                scores.add_score(doc_id, score);
+            }
         }
     }
     let top_n_results = scores.get_top_n(top_n);
 
-    let collection_path = repo_dir.join(COLLECTION_FILENAME);
-    let inv_collection = InvertedCollection::from_file(collection_path)?;
+    let store_path = repo_dir.join(STORE_FILENAME);
+    let mut store = match store_path.exists() {
+        true => Some(DocumentStore::open(store_path)?),
+        false => None,
+    };
     let top_n_results = top_n_results
         .iter()
-        .map(|(doc_id, score)| (inv_collection.get_path(**doc_id).unwrap(), *score))
-        .collect::<Vec<_>>();
+        .map(|(doc_id, score)| {
+            let path = inv_collection.get_path(**doc_id).unwrap();
+            let entry = inv_collection.get_entry(**doc_id);
+            tokenizer.set_language(stem_language(entry));
+            let title = entry
+                .and_then(|e| e.field("title"))
+                .map(FieldValue::to_string);
+            let text = match store.as_mut() {
+                Some(store) => store.get(**doc_id)?,
+                None => None,
+            };
+            // Files without an extension are typed by their stored text, which is
+            // their contents, rather than by reading them.
+            let document_type = DocumentType::from_path(path).or_else(|| {
+                let text = text.as_deref()?;
+                DocumentType::from_contents(path, &text.as_bytes()[..text.len().min(SNIFF_LEN)])
+            });
+            // Records are shown by the path of their file and their key, and
+            // passages by the path of their document and their section. The
+            // documents of the store are not read from their files.
+            let (location, section) = if let Some(key) = entry.and_then(|e| e.key()) {
+                (format!("{}#{key}", path.display()), None)
+            } else if let (Some(entry), Some(text)) = (entry, &text) {
+                let (section, first_line) = match entry.range() {
+                    Some(range) => {
+                        let section = entry.section_at(range.start);
+                        (section.map(|s| s.heading().to_string()), entry.line())
+                    }
+                    None => {
+                        let sections = entry.sections();
+                        let section = find_section(text, sections, &section_terms, &mut tokenizer);
+                        let keeps_lines = document_type.is_some_and(|dt| dt.keeps_lines());
+                        (section, keeps_lines.then_some(0))
+                    }
+                };
+                let lines = first_line.zip(document_type).and_then(|(first_line, dt)| {
+                    find_lines(text, first_line, &section_terms, &mut tokenizer, dt)
+                });
+                (location(path, lines), section)
+            } else if let Some(range) = entry.and_then(|e| e.range()) {
+                let document = Document::from_file_with_options(0, path, &config.read).ok();
+                let section = document
+                    .as_ref()
+                    .and_then(|document| passage_section(document, range.start));
+                let passage = document.as_ref().map(|document| (document, range));
+                let lines = read_lines(path, &section_terms, &mut tokenizer, passage);
+                (location(path, lines), section)
+            } else {
+                let section = read_section(path, &section_terms, &mut tokenizer, &config);
+                let lines = read_lines(path, &section_terms, &mut tokenizer, None);
+                (location(path, lines), section)
+            };
+            let snippet =
+                text.map(|text| snippet(&text, &section_terms, &mut tokenizer, document_type));
+            Ok(SearchResult {
+                location,
+                title,
+                section,
+                snippet,
+                score: **score,
+            })
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
 
     display_results(top_n_results)?;
     Ok(())
 }
 
-fn display_results(results: Vec<(&PathBuf, &f64)>) -> io::Result<()> {
+/// Tokenizes the free text of a query with the stemmer of each of the
+/// languages, starting with the default language of the tokenizer. The
+/// tokens that a language stems differently from the languages before it
+/// are added to the tokens of the query.
+fn tokenize_query(
+    text: &str,
+    tokenizer: &mut Tokenizer,
+    languages: &HashSet<Language>,
+) -> Vec<Term> {
+    let terms = |tokens: Vec<Token>| tokens.into_iter().map(Token::into_term);
+    let mut tokens = terms(tokenizer.tokenize(text)).collect::<Vec<_>>();
+    let mut other_languages = languages
+        .iter()
+        .filter(|&&language| language != tokenizer.language())
+        .copied()
+        .collect::<Vec<_>>();
+    other_languages.sort_by_key(|language| language.code());
+    let default_language = tokenizer.language();
+    for language in other_languages {
+        tokenizer.set_language(language);
+        let seen = tokens.iter().cloned().collect::<HashSet<_>>();
+        let language_tokens = terms(tokenizer.tokenize(text));
+        tokens.extend(language_tokens.filter(|t| !seen.contains(t)));
+    }
+    tokenizer.set_language(default_language);
+    tokens
+}
+
+/// Adds the terms that the single CJK characters of a query are matched by
+/// within the runs of CJK characters of the documents, i.e. the indexed
+/// bigrams that they are part of, see [`cjk_bigrams`]. If the query searches
+/// a text field, the bigrams are looked up among the terms of the field.
+fn expand_cjk(terms: &mut Vec<Term>, vocabulary: &Encoder, field: Option<&str>) {
+    let field_terms = || {
+        vocabulary.terms().filter_map(move |term| match field {
+            Some(field) => split_field_token(term)
+                .filter(|(name, _)| *name == field)
+                .map(|(_, term)| term),
+            None => Some(term),
+        })
+    };
+    let bigrams = terms
+        .iter()
+        .flat_map(|term| cjk_bigrams(term, field_terms()))
+        .filter(|bigram| !terms.iter().any(|term| term == bigram))
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    terms.extend(bigrams);
+}
+
+/// Tokenizes the exact words of a query, e.g. `Rust` of `=Rust`, to their
+/// exact terms, e.g. `=Rust`, which match the case-preserved words of the
+/// documents. Only whole words are matched, e.g. `=C++` does not match the
+/// `C` that `C++` is also indexed by.
+fn tokenize_exact(words: &[String], tokenizer: &Tokenizer) -> Vec<Term> {
+    let mut terms = Vec::new();
+    for word in words {
+        let mut whole = 0..0;
+        for token in tokenizer.pre_tokenize(word) {
+            let offsets = token.offsets();
+            if whole.start <= offsets.start && offsets.end <= whole.end {
+                continue;
+            }
+            terms.push(exact_token(token.term()));
+            whole = offsets;
+        }
+    }
+    terms
+}
+
+/// Returns the tokens of a text whose terms are query terms: the tokens of
+/// the tokenizer, and the exact tokens of the text, e.g. `=Rust`, that were
+/// not passed through its filters.
+fn match_terms(
+    text: &str,
+    terms: &HashSet<Term>,
+    tokenizer: &mut Tokenizer,
+    document_type: Option<DocumentType>,
+) -> Vec<Token> {
+    let (tokens, exact_tokens) = match document_type {
+        Some(DocumentType::Code(_)) => (
+            tokenizer.tokenize_code(text),
+            tokenizer.pre_tokenize_code(text),
+        ),
+        _ => (tokenizer.tokenize(text), tokenizer.pre_tokenize(text)),
+    };
+    let exact_tokens = exact_tokens.into_iter().map(|token| {
+        let term = exact_token(token.term());
+        token.with_term(term)
+    });
+    tokens
+        .into_iter()
+        .chain(exact_tokens)
+        .filter(|token| terms.contains(token.term()))
+        .collect()
+}
+
+/// Reads the document at a path from its file, and finds the section in
+/// which the query terms occur most often, like [`find_section`]. Documents
+/// that cannot be read have no section.
+fn read_section(
+    path: &Path,
+    terms: &HashSet<Term>,
+    tokenizer: &mut Tokenizer,
+    config: &RepoConfig,
+) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    let document = Document::from_file_with_options(0, path, &config.read).ok()?;
+    let sections = document.metadata().sections();
+    find_section(document.page_content(), sections, terms, tokenizer)
+}
+
+/// Finds the section of a document in which the query terms occur most
+/// often, e.g. the chapter of a book, and returns its heading, given the
+/// content of the document and its sections. Documents without sections
+/// have no section.
+fn find_section(
+    content: &str,
+    sections: &[Section],
+    terms: &HashSet<Term>,
+    tokenizer: &mut Tokenizer,
+) -> Option<String> {
+    if terms.is_empty() {
+        return None;
+    }
+    sections
+        .iter()
+        .enumerate()
+        .map(|(i, section)| {
+            let end = sections
+                .get(i + 1)
+                .map_or(content.len(), |next| next.offset());
+            let text = &content[section.offset()..end];
+            let count = match_terms(text, terms, tokenizer, None).len();
+            (count, section)
+        })
+        .filter(|(count, _)| *count > 0)
+        .max_by_key(|(count, _)| *count)
+        .map(|(_, section)| section.heading().to_string())
+}
+
+/// Returns the heading of the section of a document that a passage starts
+/// in, given the byte offset of the passage in the document's content.
+fn passage_section(document: &Document, offset: usize) -> Option<String> {
+    let section = document.metadata().section_at(offset)?;
+    Some(section.heading().to_string())
+}
+
+/// Reads the file of a document, and finds the lines in which the query
+/// terms occur most densely, like [`find_lines`]. Only the lines of text
+/// files are found, e.g. of Markdown documents or source files. The lines
+/// of a passage are found within the passage, if the content of its
+/// document is the text of the file.
+fn read_lines(
+    path: &Path,
+    terms: &HashSet<Term>,
+    tokenizer: &mut Tokenizer,
+    passage: Option<(&Document, Range<usize>)>,
+) -> Option<(usize, usize)> {
+    if terms.is_empty() {
+        return None;
+    }
+    let document_type = DocumentType::detect(path).ok()??;
+    if !matches!(
+        document_type,
+        DocumentType::Text
+            | DocumentType::Markdown
+            | DocumentType::Xml
+            | DocumentType::Json
+            | DocumentType::Code(_)
+    ) {
+        return None;
+    }
+    let (text, _) = read_text(path).ok()?;
+    let (text, first_line) = match passage {
+        Some((document, range)) if document.page_content() == text => {
+            let first_line = text[..range.start].matches('\n').count();
+            (&text[range], first_line)
+        }
+        _ => (text.as_str(), 0),
+    };
+    find_lines(text, first_line, terms, tokenizer, document_type)
+}
+
+/// Finds the lines of a text in which the query terms occur most densely,
+/// and returns the first and the last of them, counted from 1, given the
+/// line of the file that the text starts on, counted from 0.
+fn find_lines(
+    text: &str,
+    first_line: usize,
+    terms: &HashSet<Term>,
+    tokenizer: &mut Tokenizer,
+    document_type: DocumentType,
+) -> Option<(usize, usize)> {
+    if terms.is_empty() {
+        return None;
+    }
+    let lines = text
+        .lines()
+        .map(|line| {
+            let tokens = match_terms(line, terms, tokenizer, Some(document_type));
+            tokens.into_iter().map(Token::into_term).collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let (start, end) = best_window(&lines, WINDOW_LINES)?;
+    Some((first_line + start + 1, first_line + end + 1))
+}
+
+/// Returns a snippet of the text of a document, with the words in which the
+/// query terms occur most densely and a few words around them. The words are
+/// the positions of the tokens of the text, and the snippet is taken from the
+/// text at their offsets, with the query terms in bold. Whitespace is
+/// collapsed, and an ellipsis marks the text that is left out. If the text
+/// has no query terms, its first words are returned.
+fn snippet(
+    text: &str,
+    terms: &HashSet<Term>,
+    tokenizer: &mut Tokenizer,
+    document_type: Option<DocumentType>,
+) -> String {
+    // The unfiltered tokens have every position, e.g. of the stop words.
+    let tokens = match document_type {
+        Some(DocumentType::Code(_)) => tokenizer.pre_tokenize_code(text),
+        _ => tokenizer.pre_tokenize(text),
+    };
+    let mut words = Vec::new();
+    for token in tokens {
+        words.resize(token.position() + 1, 0..0);
+        words[token.position()] = token.offsets();
+    }
+    let mut word_terms = vec![Vec::new(); words.len()];
+    let mut matches = Vec::new();
+    for token in match_terms(text, terms, tokenizer, document_type) {
+        matches.push(token.offsets());
+        word_terms[token.position()].push(token.into_term());
+    }
+    let (start, end) = match best_window(&word_terms, SNIPPET_WORDS) {
+        Some((first, last)) => (
+            first.saturating_sub(SNIPPET_CONTEXT),
+            (last + SNIPPET_CONTEXT + 1).min(words.len()),
+        ),
+        None => (0, SNIPPET_WORDS.min(words.len())),
+    };
+    if start == end {
+        return String::new();
+    }
+    let range = words[start].start..words[end - 1].end;
+    let mut snippet = highlight(text, range, matches)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if start > 0 {
+        snippet.insert_str(0, "… ");
+    }
+    if end < words.len() {
+        snippet.push_str(" …");
+    }
+    snippet
+}
+
+/// Returns a range of a text, with the matches in it in bold. Matches that
+/// overlap, e.g. an identifier and a word of it, are merged.
+fn highlight(text: &str, range: Range<usize>, mut matches: Vec<Range<usize>>) -> String {
+    matches.retain(|m| range.start <= m.start && m.end <= range.end);
+    matches.sort_by_key(|m| (m.start, m.end));
+    let mut highlighted = String::new();
+    let mut offset = range.start;
+    let mut matches = matches.into_iter().peekable();
+    while let Some(mut m) = matches.next() {
+        while let Some(next) = matches.next_if(|next| next.start <= m.end) {
+            m.end = m.end.max(next.end);
+        }
+        highlighted.push_str(&text[offset..m.start]);
+        highlighted.push_str(&format!("\x1b[1m{}\x1b[0m", &text[m.clone()]));
+        offset = m.end;
+    }
+    highlighted.push_str(&text[offset..range.end]);
+    highlighted
+}
+
+/// Finds the window of up to `size` units of text, e.g. lines or words,
+/// with the most distinct terms, and then with the most occurrences of
+/// terms, given the terms of each unit. The first and the last unit of the
+/// window that have terms are returned, counted from 0. If no unit has
+/// terms, `None` is returned.
+fn best_window<T: Eq + Hash>(units: &[Vec<T>], size: usize) -> Option<(usize, usize)> {
+    let mut counts: HashMap<&T, usize> = HashMap::new();
+    let mut total = 0;
+    let mut best: Option<((usize, usize), usize)> = None;
+    for (end, unit) in units.iter().enumerate() {
+        for term in unit {
+            *counts.entry(term).or_default() += 1;
+            total += 1;
+        }
+        if end >= size {
+            for term in &units[end - size] {
+                if let Some(count) = counts.get_mut(term) {
+                    *count -= 1;
+                    if *count == 0 {
+                        counts.remove(term);
+                    }
+                }
+                total -= 1;
+            }
+        }
+        let score = (counts.len(), total);
+        if total > 0 && best.is_none_or(|(best_score, _)| score > best_score) {
+            best = Some((score, end));
+        }
+    }
+    let (_, end) = best?;
+    let start = (end + 1).saturating_sub(size);
+    let first = (start..=end).find(|&idx| !units[idx].is_empty())?;
+    Some((first, end))
+}
+
+/// Formats the location of a result as its path, followed by its lines if
+/// it has any, e.g. `manual.md:120-138`, or `manual.md:120` for one line.
+fn location(path: &Path, lines: Option<(usize, usize)>) -> String {
+    match lines {
+        Some((start, end)) if start == end => format!("{}:{start}", path.display()),
+        Some((start, end)) => format!("{}:{start}-{end}", path.display()),
+        None => path.display().to_string(),
+    }
+}
+
+fn display_results(results: Vec<SearchResult>) -> io::Result<()> {
     let stdout = io::stdout();
     let stdout = stdout.lock();
     let mut tw = tabwriter::TabWriter::new(stdout).padding(2);
-    writeln!(tw, "\tNo\tPath\tScore")?;
-    for (i, (path, score)) in results.iter().enumerate() {
-        writeln!(tw, "\t{i}\t{}\t{score}", path.display())?;
+    writeln!(tw, "\tNo\tPath\tTitle\tSection\tScore\tSnippet")?;
+    for (i, result) in results.iter().enumerate() {
+        let title = result.title.as_deref().unwrap_or("-");
+        let section = result.section.as_deref().unwrap_or("-");
+        let snippet = result.snippet.as_deref().unwrap_or("-");
+        writeln!(
+            tw,
+            "\t{i}\t{}\t{title}\t{section}\t{}\t{snippet}",
+            result.location, result.score
+        )?;
     }
     tw.flush()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_best_window() {
+        let mut lines = vec![Vec::new(); 60];
+        lines[2] = vec!["parse"];
+        lines[30] = vec!["parse", "parse"];
+        lines[35] = vec!["config"];
+        lines[58] = vec!["parse"];
+        assert_eq!(best_window(&lines, WINDOW_LINES), Some((30, 35)));
+        assert_eq!(best_window(&lines[..10], WINDOW_LINES), Some((2, 2)));
+        assert_eq!(best_window(&lines, 3), Some((30, 30)));
+        assert_eq!(best_window::<&str>(&[vec![], vec![]], WINDOW_LINES), None);
+    }
+
+    #[test]
+    fn test_snippet() {
+        let mut tokenizer = Tokenizer::default();
+        let terms = HashSet::from(["index".to_string()]);
+        let text = "one two three four five six seven eight nine ten eleven twelve \
+            the index, is stored\n\nin the repository directory";
+        assert_eq!(
+            snippet(text, &terms, &mut tokenizer, None),
+            "… ten eleven twelve the \x1b[1mindex\x1b[0m, is stored in the …"
+        );
+        let text = "no terms here";
+        assert_eq!(snippet(text, &terms, &mut tokenizer, None), "no terms here");
+    }
+
+    #[test]
+    fn test_highlight() {
+        let text = "let cfg = parseConfig(path);";
+        let matches = vec![10..21, 15..21, 22..26];
+        assert_eq!(
+            highlight(text, 4..27, matches),
+            "cfg = \x1b[1mparseConfig\x1b[0m(\x1b[1mpath\x1b[0m)"
+        );
+    }
+
+    #[test]
+    fn test_tokenize_exact() {
+        let tokenizer = Tokenizer::default();
+        let words = ["Rust".to_string(), "C++".to_string(), "node.js".to_string()];
+        let terms = tokenize_exact(&words, &tokenizer);
+        assert_eq!(terms, ["=Rust", "=C++", "=node.js"]);
+        let words = ["東京タワー".to_string()];
+        assert_eq!(tokenize_exact(&words, &tokenizer).len(), 4);
+    }
+
+    #[test]
+    fn test_exact_snippet() {
+        let mut tokenizer = Tokenizer::default();
+        let terms = HashSet::from(["=Rust".to_string()]);
+        let text = "rust on the Rust bridge";
+        assert_eq!(
+            snippet(text, &terms, &mut tokenizer, None),
+            "rust on the \x1b[1mRust\x1b[0m bridge"
+        );
+    }
+
+    #[test]
+    fn test_tokenize_query() {
+        let mut tokenizer = Tokenizer::default();
+        let languages = HashSet::from([Language::English, Language::German]);
+        let tokens = tokenize_query("häuser running", &mut tokenizer, &languages);
+        assert_eq!(tokens, ["häuser", "run", "haus", "running"]);
+        assert_eq!(tokenizer.language(), Language::English);
+    }
+
+    #[test]
+    fn test_expand_cjk() {
+        let mut vocabulary = Encoder::new();
+        for term in ["子猫", "猫が", "水", "comments:黒猫", "猫"] {
+            vocabulary.encode(term);
+        }
+        let mut tokenizer = Tokenizer::default();
+        let languages = HashSet::from([Language::English]);
+        let mut tokens = tokenize_query("猫 cat", &mut tokenizer, &languages);
+        expand_cjk(&mut tokens, &vocabulary, None);
+        assert_eq!(tokens, ["猫", "cat", "子猫", "猫が"]);
+
+        let mut tokens = vec!["猫".to_string()];
+        expand_cjk(&mut tokens, &vocabulary, Some("comments"));
+        assert_eq!(tokens, ["猫", "黒猫"]);
+    }
+
+    #[test]
+    fn test_find_section() {
+        let mut tokenizer = Tokenizer::default();
+        let terms = HashSet::from(["index".to_string()]);
+        let content = "Intro\nThe intro.\nUsage\nIndex the files, then search the index.";
+        let sections = vec![
+            Section::new("Intro".to_string(), 1, 0),
+            Section::new("Usage".to_string(), 1, 17),
+        ];
+        let section = find_section(content, &sections, &terms, &mut tokenizer);
+        assert_eq!(section.as_deref(), Some("Usage"));
+        assert_eq!(find_section(content, &[], &terms, &mut tokenizer), None);
+    }
+
+    #[test]
+    fn test_find_lines() {
+        let mut tokenizer = Tokenizer::default();
+        let terms = HashSet::from(["index".to_string()]);
+        let text = "fn main() {\n    build_index();\n}\n";
+        let lines = find_lines(text, 0, &terms, &mut tokenizer, DocumentType::Text);
+        assert_eq!(lines, Some((2, 2)));
+        let lines = find_lines(text, 10, &terms, &mut tokenizer, DocumentType::Text);
+        assert_eq!(lines, Some((12, 12)));
+    }
+
+    #[test]
+    fn test_location() {
+        let path = Path::new("manual.md");
+        assert_eq!(location(path, Some((120, 138))), "manual.md:120-138");
+        assert_eq!(location(path, Some((7, 7))), "manual.md:7");
+        assert_eq!(location(path, None), "manual.md");
+    }
+}
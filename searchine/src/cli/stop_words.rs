@@ -0,0 +1,77 @@
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::Context;
+use tabwriter::TabWriter;
+
+use documents::read::code::{COMMENTS_FIELD, STRINGS_FIELD};
+use index::inverted::freq::FrequencyIndex;
+use index::inverted::Index;
+use tokenize::{split_field_token, Encoder};
+
+use crate::config::{INDEX_FILENAME, VOCABULARY_FILENAME};
+
+/// Suggests stop words for the repository, from the terms of its index
+/// that occur in at least `min_ratio` of the documents. The terms are the
+/// stemmed tokens of the index, so the stop words that are already dropped
+/// are not suggested. The terms of the text fields and the exact terms are
+/// skipped.
+pub fn suggest(repo_dir: impl AsRef<Path>, min_ratio: f64) -> anyhow::Result<()> {
+    let repo_dir = repo_dir.as_ref();
+    let index = FrequencyIndex::from_file(repo_dir.join(INDEX_FILENAME))?;
+    let vocabulary_path = repo_dir.join(VOCABULARY_FILENAME);
+    let vocabulary = Encoder::from_file(&vocabulary_path).context(format!(
+        "Failed to read vocabulary from file: {}",
+        vocabulary_path.display()
+    ))?;
+    let doc_frequencies = index
+        .doc_frequencies()
+        .filter_map(|(token_id, n_docs)| Some((vocabulary.decode(token_id)?, n_docs)))
+        .filter(|(term, _)| !is_field_term(term) && !term.starts_with('='))
+        .map(|(term, n_docs)| (term.to_string(), n_docs));
+    let suggested = tokenize::stop::suggest(doc_frequencies, index.n_docs(), min_ratio);
+    if suggested.is_empty() {
+        println_bold!(
+            "No terms occur in {:.0}% of the documents or more.",
+            min_ratio * 100.0
+        );
+        return Ok(());
+    }
+    display_suggested(&suggested)?;
+    println_bold!(
+        "Add the stop words to the `words` of the `stop_words` filter in the config, and re-index."
+    );
+    Ok(())
+}
+
+/// Returns whether the term is a term of a text field, e.g. `comments:todo`,
+/// rather than a term of the page content, e.g. a URL.
+fn is_field_term(term: &str) -> bool {
+    split_field_token(term)
+        .is_some_and(|(field, _)| [COMMENTS_FIELD, STRINGS_FIELD].contains(&field))
+}
+
+fn display_suggested(suggested: &[(String, f64)]) -> io::Result<()> {
+    let stdout = io::stdout();
+    let stdout = stdout.lock();
+    let mut tw = TabWriter::new(stdout).padding(2);
+    writeln!(tw, "\tTerm\tDocuments")?;
+    for (term, ratio) in suggested {
+        writeln!(tw, "\t{term}\t{:.1}%", ratio * 100.0)?;
+    }
+    tw.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_field_term() {
+        assert!(is_field_term("comments:todo"));
+        assert!(is_field_term("strings:=Config"));
+        assert!(!is_field_term("https://example.com"));
+        assert!(!is_field_term("mailto:info"));
+        assert!(!is_field_term("index"));
+    }
+}
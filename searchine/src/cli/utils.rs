@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 
+use documents::read::archive::{self, MemberHashes};
 use index::collection::Collection;
 
 /// Fetches the paths of the files that have been removed from the directory,
@@ -30,17 +31,18 @@ pub fn fetch_new_files(corpus_index: &Collection, dir: &[PathBuf]) -> Vec<PathBu
 
 /// Fetches the paths of the files that have been modified in the directory,
 /// compared to the corpus index.
+///
+/// Members of archives are modified if their archive has been modified, and
+/// the hash of their contents has changed.
 pub fn fetch_modified_files(
     corpus_index: &Collection,
     dir: &[PathBuf],
 ) -> anyhow::Result<Vec<PathBuf>> {
     let mut modified_paths = vec![];
+    let mut hashes = MemberHashes::new();
     for path in dir {
         if corpus_index.contains_path(path) {
-            let metadata = path
-                .metadata()
-                .context(format!("Failed to get metadata for: {}.", path.display()))?;
-            let current_modified = metadata.modified().context(format!(
+            let current_modified = archive::modified(path).context(format!(
                 "Failed to get last modified time for: {}.",
                 path.display()
             ))?;
@@ -49,7 +51,13 @@ pub fn fetch_modified_files(
                 path.display()
             ))?;
             if current_modified > index_modified {
-                modified_paths.push(path.clone());
+                let current_hash = hashes.get(path).context(format!(
+                    "Failed to hash the contents of: {}.",
+                    path.display()
+                ))?;
+                if current_hash.is_none() || current_hash != corpus_index.get_hash(path) {
+                    modified_paths.push(path.clone());
+                }
             }
         }
     }
@@ -1,5 +1,135 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use documents::split::SplitOptions;
+use documents::ReadOptions;
+use tokenize::filter::FilterOptions;
+use tokenize::stem::StemOptions;
+use tokenize::{LanguageOptions, Tokenizer, TokenizerOptions};
+
 pub const SEARCHINE_PATH: &str = ".searchine";
 pub const COLLECTION_FILENAME: &str = "collection.json";
 pub const INDEX_FILENAME: &str = "index.json";
+pub const CONFIG_FILENAME: &str = "config.json";
+pub const STORE_FILENAME: &str = "store.bin";
+pub const VOCABULARY_FILENAME: &str = "vocabulary.json";
+pub const SETTINGS_FILENAME: &str = "settings.json";
 
 pub const CHANNEL_BOUND: usize = 1024;
+
+/// The configuration of a searchine repository.
+///
+/// It is stored in the repository, and missing fields take their
+/// default values, so that repositories created with older versions
+/// keep working.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepoConfig {
+    /// Options for reading the contents of the documents.
+    pub read: ReadOptions,
+    /// Options for splitting the documents into passages, which are
+    /// indexed and searched instead of the whole documents. If `None`,
+    /// the documents are not split.
+    pub passages: Option<SplitOptions>,
+    /// Whether the extracted text of each indexed document is kept in a
+    /// compressed document store, from which the snippets of the search
+    /// results are taken.
+    pub store: bool,
+    /// The chain of token filters that the tokens of the documents and of
+    /// the queries are passed through, e.g. to drop the stop words.
+    pub tokenizer: TokenizerOptions,
+    /// Options for the language that the documents are stemmed in.
+    pub language: LanguageOptions,
+}
+
+impl RepoConfig {
+    /// Loads the configuration of the repository at the specified path.
+    /// If the repository has no configuration file, the default
+    /// configuration is returned.
+    ///
+    /// The relative paths of the files of the token filters, e.g. the
+    /// dictionary of a lemmatizer, are relative to the repository.
+    pub fn from_repo(repo_dir: impl AsRef<Path>) -> io::Result<Self> {
+        let repo_dir = repo_dir.as_ref();
+        let path = repo_dir.join(CONFIG_FILENAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let reader = BufReader::new(File::open(path)?);
+        let mut config: Self = serde_json::from_reader(reader)?;
+        for filter in &mut config.tokenizer.filters {
+            if let FilterOptions::Stem(StemOptions {
+                dictionary: Some(path),
+                ..
+            }) = filter
+            {
+                *path = repo_dir.join(&path);
+            }
+        }
+        Ok(config)
+    }
+
+    /// Writes the configuration to the repository at the specified path.
+    pub fn into_repo(self, repo_dir: impl AsRef<Path>) -> io::Result<()> {
+        let path = repo_dir.as_ref().join(CONFIG_FILENAME);
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &self)?;
+        Ok(())
+    }
+}
+
+/// The settings that the index of a repository was created with, i.e. how
+/// its documents were tokenized. They are recorded with the index, so that
+/// queries are tokenized the same way as the documents, even if the
+/// configuration changes after indexing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexSettings {
+    /// The chain of token filters of the documents.
+    pub tokenizer: TokenizerOptions,
+    /// The options for the language that the documents were stemmed in.
+    pub language: LanguageOptions,
+}
+
+impl IndexSettings {
+    /// Returns the settings that the configuration of a repository
+    /// specifies for its index.
+    pub fn from_config(config: &RepoConfig) -> Self {
+        Self {
+            tokenizer: config.tokenizer.clone(),
+            language: config.language,
+        }
+    }
+
+    /// Loads the settings of the index of the repository at the specified
+    /// path. If the index was created without recording its settings, e.g.
+    /// by an older version, `None` is returned.
+    pub fn from_repo(repo_dir: impl AsRef<Path>) -> io::Result<Option<Self>> {
+        let path = repo_dir.as_ref().join(SETTINGS_FILENAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let reader = BufReader::new(File::open(path)?);
+        Ok(Some(serde_json::from_reader(reader)?))
+    }
+
+    /// Returns a tokenizer with the chain of token filters of the index,
+    /// so that the documents and the queries are tokenized the same way.
+    /// It stems in the default language. It fails if a filter cannot be
+    /// created, e.g. if the dictionary of a lemmatizer cannot be loaded.
+    pub fn tokenizer(&self) -> io::Result<Tokenizer> {
+        let tokenizer = self.tokenizer.tokenizer()?;
+        Ok(tokenizer.with_language(self.language.default))
+    }
+
+    /// Writes the settings to the repository at the specified path.
+    pub fn into_repo(self, repo_dir: impl AsRef<Path>) -> io::Result<()> {
+        let path = repo_dir.as_ref().join(SETTINGS_FILENAME);
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &self)?;
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 
 use walkdir::{DirEntry, WalkDir};
 
+use documents::read::archive::{self, ArchiveFormat, MemberPath};
 use documents::DocumentType;
 
 use crate::path::get_relative_path;
@@ -17,6 +18,9 @@ use crate::path::get_relative_path;
 /// * Files that are currently not supported for parsing.
 /// * TODO: Add more filters.
 ///
+/// Archives, e.g. `bundle.zip` or `bundle.tar.gz`, are walked too, and
+/// their supported members have paths such as `bundle.zip!/docs/intro.md`.
+///
 /// Additionally, it provides methods for presenting the paths to
 /// the files as relative to the current working directory.
 #[derive(Debug)]
@@ -75,9 +79,11 @@ impl Directory {
     ///
     /// Hidden directories and files (starting with a dot `.`) are ignored,
     /// and entries that cause errors are skipped printing an error message.
+    /// Archives are replaced by the paths to their supported members.
     pub fn iter_full_paths(&self, verbose: bool) -> impl Iterator<Item = PathBuf> {
         self.iter_relative_paths(verbose)
             .filter_map(|path| path.canonicalize().ok())
+            .flat_map(move |path| expand_archive(path, verbose))
     }
 
     /// Returns an iterator of the paths to the files in the directory,
@@ -104,15 +110,47 @@ fn is_hidden(entry: &DirEntry) -> bool {
 
 /// Checks if a directory entry is a supported file type.
 ///
-/// A file type is considered supported if its extension matches one of the
-/// supported document types.
+/// The type of a file is detected from its extension and the first bytes of
+/// its contents, so text files without an extension, e.g. `README`, are
+/// supported, while binary files with the extension of a text file are not.
+/// Compressed files are supported if the file that they compress is, e.g.
+/// `notes.md.gz`, and archives are supported too.
 fn is_supported_file_type(path: impl AsRef<Path>) -> bool {
-    let extension = path.as_ref().extension();
-    if let Some(extension) = extension {
-        DocumentType::from_extension(extension).is_some()
-    } else {
-        false
+    let path = path.as_ref();
+    ArchiveFormat::from_path(path).is_some() || matches!(DocumentType::detect(path), Ok(Some(_)))
+}
+
+/// Returns the paths to the supported members of an archive, or the path
+/// itself if it is not an archive. Archives that cannot be read are skipped
+/// printing an error message, and archives inside archives are ignored.
+fn expand_archive(path: PathBuf, verbose: bool) -> Vec<PathBuf> {
+    if ArchiveFormat::from_path(&path).is_none() {
+        return vec![path];
     }
+    let members = match archive::detect_members(&path) {
+        Ok(members) => members,
+        Err(err) => {
+            eprintln!(
+                "ERROR: Skipping archive {} because: {}",
+                path.display(),
+                err
+            );
+            return vec![];
+        }
+    };
+    members
+        .into_iter()
+        .filter_map(|(member, document_type)| {
+            let member_path = MemberPath::new(&path, &member).to_path_buf();
+            if document_type.is_none() && verbose {
+                eprintln!(
+                    "WARNING: Ignoring unsupported file: {}",
+                    member_path.display()
+                );
+            }
+            document_type.map(|_| member_path)
+        })
+        .collect()
 }
 
 /// Checks if a directory entry is ignored. A directory entry is ignored if
@@ -72,6 +72,7 @@ fn main() -> anyhow::Result<()> {
             query,
             dir_path,
             top_n,
+            field,
         } => {
             let dir_path = canonicalize_dir_path(dir_path)?;
             if let Some(repo_path) = find_repo_path(&dir_path, SEARCHINE_PATH) {
@@ -84,7 +85,27 @@ fn main() -> anyhow::Result<()> {
                     return Ok(());
                 }
                 let top_n = top_n.unwrap_or(10);
-                cli::search::invoke(repo_path, &query, top_n)?;
+                cli::search::invoke(repo_path, &query, field.as_deref(), top_n)?;
+            } else {
+                println_bold!("Index repository does not exist at: {}", dir_path.display());
+            }
+        }
+        // Suggests stop words from the document frequencies of the terms in the index.
+        Commands::SuggestStopWords {
+            dir_path,
+            min_ratio,
+        } => {
+            let dir_path = canonicalize_dir_path(dir_path)?;
+            if let Some(repo_path) = find_repo_path(&dir_path, SEARCHINE_PATH) {
+                if !repo_path.join(INDEX_FILENAME).exists() {
+                    println_bold!(
+                        "{} {}",
+                        "Index repository has not been indexed.",
+                        "Run `searchine index` to index the repository."
+                    );
+                    return Ok(());
+                }
+                cli::stop_words::suggest(repo_path, min_ratio)?;
             } else {
                 println_bold!("Index repository does not exist at: {}", dir_path.display());
             }
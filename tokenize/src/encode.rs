@@ -3,50 +3,109 @@ use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use std::{fs, io};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-type TokenId = u32;
+use crate::Term;
 
-#[derive(Default, Serialize, Deserialize)]
-pub(crate) struct Encoder {
-    vocabulary: HashMap<String, TokenId>,
+/// The integer ID of a term, which the index uses instead of the term.
+pub type TokenId = u32;
+
+/// The vocabulary of an index, which maps each term to its token ID and
+/// back. The token IDs are assigned in the order in which the terms are
+/// first encoded, from 0.
+///
+/// The vocabulary is persisted as the list of its terms, in the order of
+/// their token IDs.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::Encoder;
+///
+/// let mut encoder = Encoder::new();
+/// assert_eq!(encoder.encode("index"), 0);
+/// assert_eq!(encoder.encode("search"), 1);
+/// assert_eq!(encoder.encode("index"), 0);
+/// assert_eq!(encoder.get("search"), Some(1));
+/// assert_eq!(encoder.decode(1), Some("search"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Encoder {
+    vocabulary: HashMap<Term, TokenId>,
+    terms: Vec<Term>,
 }
 
 impl Encoder {
+    /// Creates a new, empty encoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an encoder from its terms, in the order of their token IDs.
+    fn from_terms(terms: Vec<Term>) -> Self {
+        let vocabulary = terms
+            .iter()
+            .enumerate()
+            .map(|(token_id, term)| (term.clone(), token_id as TokenId))
+            .collect();
+        Self { vocabulary, terms }
+    }
+
     /// Encodes a token.
     ///
     /// If the token is in the encoder's vocabulary, then the token ID
     /// is returned. Otherwise, it's added and the token ID is returned.
-    pub(crate) fn encode(&mut self, token: &str) -> TokenId {
-        let term_id = self.vocabulary.len() as u32;
-        *self.vocabulary.entry(token.to_string()).or_insert(term_id)
+    pub fn encode(&mut self, token: &str) -> TokenId {
+        if let Some(&token_id) = self.vocabulary.get(token) {
+            return token_id;
+        }
+        let token_id = self.terms.len() as TokenId;
+        self.vocabulary.insert(token.to_string(), token_id);
+        self.terms.push(token.to_string());
+        token_id
+    }
+
+    /// Returns the token ID of a token, if the token is in the encoder's
+    /// vocabulary, without adding it, e.g. for the tokens of a query.
+    pub fn get(&self, token: &str) -> Option<TokenId> {
+        self.vocabulary.get(token).copied()
     }
 
     /// Decodes a token ID.
     ///
     /// If the token ID is in the encoder's vocabulary, a shared reference
     /// to the corresponding token is returned.
-    #[allow(dead_code)]
-    fn decode(&self, token_id: &TokenId) -> Option<&str> {
-        self.vocabulary.iter().find_map(|(key, &value)| {
-            if *token_id == value {
-                Some(key.as_str())
-            } else {
-                None
-            }
-        })
+    pub fn decode(&self, token_id: TokenId) -> Option<&str> {
+        self.terms.get(token_id as usize).map(String::as_str)
+    }
+
+    /// Returns the terms of the encoder's vocabulary, in the order of their
+    /// token IDs.
+    pub fn terms(&self) -> impl Iterator<Item = &str> {
+        self.terms.iter().map(String::as_str)
+    }
+
+    /// Returns the number of terms in the encoder's vocabulary.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Returns true if the encoder's vocabulary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
     }
 
     /// Writes the encoder's vocabulary to file at specified path.
-    pub(crate) fn into_file(self, path: impl AsRef<Path>) -> io::Result<()> {
+    pub fn into_file(self, path: impl AsRef<Path>) -> io::Result<()> {
         let file = fs::File::create(path)?;
         let writer = BufWriter::new(file);
         serde_json::to_writer(writer, &self)?;
         Ok(())
     }
 
-    /// Creates an encoder from
-    pub(crate) fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+    /// Creates an encoder from the vocabulary in the file at the specified
+    /// path.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
         let file = fs::File::open(path)?;
         let reader = BufReader::new(file);
         let encoder = serde_json::from_reader(reader)?;
@@ -54,6 +113,18 @@ impl Encoder {
     }
 }
 
+impl Serialize for Encoder {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.terms.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Encoder {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<Term>::deserialize(deserializer).map(Self::from_terms)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -71,10 +142,24 @@ mod tests {
         assert_eq!(encoder.encode("it"), 2);
 
         // Decode
-        assert_eq!(encoder.decode(&0), Some("i"));
-        assert_eq!(encoder.decode(&1), Some("want"));
-        assert_eq!(encoder.decode(&2), Some("it"));
-        assert_eq!(encoder.decode(&3), Some("got"));
-        assert_eq!(encoder.decode(&4), None);
+        assert_eq!(encoder.decode(0), Some("i"));
+        assert_eq!(encoder.decode(1), Some("want"));
+        assert_eq!(encoder.decode(2), Some("it"));
+        assert_eq!(encoder.decode(3), Some("got"));
+        assert_eq!(encoder.decode(4), None);
+        assert_eq!(encoder.get("ai"), None);
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut encoder = Encoder::new();
+        encoder.encode("index");
+        encoder.encode("search");
+        let json = serde_json::to_string(&encoder).unwrap();
+        assert_eq!(json, r#"["index","search"]"#);
+        let encoder: Encoder = serde_json::from_str(&json).unwrap();
+        assert_eq!(encoder.get("search"), Some(1));
+        assert_eq!(encoder.decode(0), Some("index"));
+        assert_eq!(encoder.len(), 2);
     }
 }
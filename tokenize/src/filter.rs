@@ -0,0 +1,558 @@
+use std::collections::HashMap;
+use std::io;
+
+use rust_stemmers::Stemmer;
+use serde::{Deserialize, Serialize};
+
+use crate::language::Language;
+use crate::normalize::fold;
+use crate::stem::{light_stem, Lemmatizer, StemOptions, StemmerKind};
+use crate::stop::{StopWordOptions, StopWords};
+use crate::{Term, Token};
+
+/// A stage of a tokenizer, that transforms the tokens that the stages
+/// before it produced. A filter may change the terms of tokens, drop
+/// tokens, or add new ones, e.g. the synonyms of a token, which keep the
+/// offsets and position of the token.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::filter::TokenFilter;
+/// use tokenize::{Token, Tokenizer};
+///
+/// /// Drops the tokens that are numbers.
+/// struct NumberFilter;
+///
+/// impl TokenFilter for NumberFilter {
+///     fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+///         tokens
+///             .into_iter()
+///             .filter(|token| !token.term().chars().all(|c| c.is_numeric()))
+///             .collect()
+///     }
+/// }
+///
+/// let mut tokenizer = Tokenizer::new().with_filter(NumberFilter);
+/// let tokens = tokenizer.tokenize("Released 2024");
+/// assert_eq!(tokens, [Token::new("Released", 0..8, 0)]);
+/// ```
+pub trait TokenFilter: Send {
+    /// Filters the tokens, in the order they occur in the text.
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token>;
+
+    /// Changes the language of the text that is filtered, e.g. to the
+    /// language of each document. Filters that do not depend on the
+    /// language ignore it.
+    fn set_language(&mut self, _language: Language) {}
+
+    /// Returns true if the filter drops the lowercase word as a stop word,
+    /// so that streams of tokens that are not passed through the filters,
+    /// e.g. the exact terms of a document, can leave it out too.
+    fn is_stop_word(&self, _word: &str) -> bool {
+        false
+    }
+}
+
+/// A filter that lowercases the tokens.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LowercaseFilter;
+
+impl TokenFilter for LowercaseFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .map(|token| {
+                let term = token.term().to_lowercase();
+                token.with_term(term)
+            })
+            .collect()
+    }
+}
+
+/// A filter that drops the stop words. Besides its own stop words, it drops
+/// the built-in stop words of the language of the text, e.g. the German stop
+/// words of a document that is detected to be German, if it uses built-in
+/// stop words at all. The stop words are lowercase, so the filter follows
+/// the lowercase filter.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::filter::{StopWordFilter, TokenFilter};
+/// use tokenize::language::Language;
+/// use tokenize::stop::StopWords;
+///
+/// let stop_words = StopWords::new().with_language(Language::English);
+/// let mut filter = StopWordFilter::new(stop_words, true);
+/// assert!(filter.is_stop_word("the"));
+/// assert!(!filter.is_stop_word("der"));
+/// filter.set_language(Language::German);
+/// assert!(filter.is_stop_word("der"));
+/// assert!(filter.is_stop_word("the"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StopWordFilter {
+    stop_words: StopWords,
+    builtin: bool,
+    language: Option<Language>,
+    languages: HashMap<Language, StopWords>,
+}
+
+impl StopWordFilter {
+    /// Creates a new filter that drops the stop words, and also the built-in
+    /// stop words of the language of the text if `builtin` is set.
+    pub fn new(stop_words: StopWords, builtin: bool) -> Self {
+        Self {
+            stop_words,
+            builtin,
+            ..Self::default()
+        }
+    }
+
+    /// Creates the filter that the options describe. The built-in stop
+    /// words of the language of the text are dropped, unless the options
+    /// use no built-in stop words, i.e. only the words of their own.
+    pub fn from_options(options: &StopWordOptions) -> Self {
+        let builtin = !options.languages.is_empty();
+        Self::new(StopWords::from_options(options), builtin)
+    }
+}
+
+impl TokenFilter for StopWordFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .filter(|token| !self.is_stop_word(token.term()))
+            .collect()
+    }
+
+    /// Switches to the built-in stop words of the language, which are
+    /// created the first time that the language is set.
+    fn set_language(&mut self, language: Language) {
+        if !self.builtin {
+            return;
+        }
+        self.language = Some(language);
+        self.languages
+            .entry(language)
+            .or_insert_with(|| StopWords::new().with_language(language));
+    }
+
+    fn is_stop_word(&self, word: &str) -> bool {
+        let language = self
+            .language
+            .and_then(|language| self.languages.get(&language));
+        self.stop_words.contains(word) || language.is_some_and(|words| words.contains(word))
+    }
+}
+
+/// A filter that stems the tokens, by default with the Snowball stemmer
+/// of their language. Stemmers expect lowercase tokens.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::filter::StemFilter;
+/// use tokenize::stem::Lemmatizer;
+/// use tokenize::Tokenizer;
+///
+/// let lemmatizer = Lemmatizer::new().with_lemma("better", "good");
+/// let mut tokenizer = Tokenizer::new().with_filter(StemFilter::lemmatizer(lemmatizer));
+/// let tokens = tokenizer.tokenize("better");
+/// assert_eq!(tokens[0].term(), "good");
+/// ```
+pub struct StemFilter {
+    language: Language,
+    stemmer: StemFilterKind,
+}
+
+/// The stemmer of a stem filter.
+enum StemFilterKind {
+    Snowball(Stemmer),
+    Light,
+    Lemmatizer(Lemmatizer),
+    Off,
+}
+
+impl StemFilter {
+    /// Creates a new filter that stems the tokens with the Snowball stemmer
+    /// of the language.
+    pub fn new(language: Language) -> Self {
+        Self {
+            language,
+            stemmer: StemFilterKind::Snowball(Stemmer::create(language.algorithm())),
+        }
+    }
+
+    /// Creates a new filter that stems the tokens lightly, by removing the
+    /// plural endings of English words.
+    pub fn light() -> Self {
+        Self {
+            language: Language::default(),
+            stemmer: StemFilterKind::Light,
+        }
+    }
+
+    /// Creates a new filter that reduces the tokens to their lemmas.
+    pub fn lemmatizer(lemmatizer: Lemmatizer) -> Self {
+        Self {
+            language: Language::default(),
+            stemmer: StemFilterKind::Lemmatizer(lemmatizer),
+        }
+    }
+
+    /// Creates a new filter that keeps the tokens as they are, so that
+    /// stemming can be turned off in a configuration.
+    pub fn off() -> Self {
+        Self {
+            language: Language::default(),
+            stemmer: StemFilterKind::Off,
+        }
+    }
+
+    /// Creates the filter that the options describe. The dictionary of a
+    /// lemmatizer is loaded from its file.
+    pub fn from_options(options: &StemOptions) -> io::Result<Self> {
+        match options.stemmer {
+            StemmerKind::Snowball => Ok(Self::new(Language::default())),
+            StemmerKind::Light => Ok(Self::light()),
+            StemmerKind::Lemmatizer => {
+                let path = options.dictionary.as_ref().ok_or_else(|| {
+                    let msg = "the lemmatizer requires a dictionary file";
+                    io::Error::new(io::ErrorKind::InvalidInput, msg)
+                })?;
+                Ok(Self::lemmatizer(Lemmatizer::from_file(path)?))
+            }
+            StemmerKind::Off => Ok(Self::off()),
+        }
+    }
+
+    /// Stems a term.
+    fn stem(&self, term: &str) -> String {
+        match &self.stemmer {
+            StemFilterKind::Snowball(stemmer) => stemmer.stem(term).into_owned(),
+            StemFilterKind::Light => light_stem(term),
+            StemFilterKind::Lemmatizer(lemmatizer) => lemmatizer.lemma(term).to_string(),
+            StemFilterKind::Off => term.to_string(),
+        }
+    }
+}
+
+impl TokenFilter for StemFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        if matches!(self.stemmer, StemFilterKind::Off) {
+            return tokens;
+        }
+        tokens
+            .into_iter()
+            .map(|token| {
+                let term = self.stem(token.term());
+                token.with_term(term)
+            })
+            .collect()
+    }
+
+    /// Changes the language of the Snowball stemmer. The other stemmers do
+    /// not depend on the language.
+    fn set_language(&mut self, language: Language) {
+        if language == self.language {
+            return;
+        }
+        self.language = language;
+        if let StemFilterKind::Snowball(stemmer) = &mut self.stemmer {
+            *stemmer = Stemmer::create(language.algorithm());
+        }
+    }
+}
+
+/// A filter that folds the tokens to ASCII where it can, e.g. `café` to
+/// `cafe`, so that text matches however its accents were typed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FoldFilter;
+
+impl TokenFilter for FoldFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .map(|token| {
+                let term = fold(token.term());
+                token.with_term(term)
+            })
+            .collect()
+    }
+}
+
+/// A filter that drops the tokens with fewer than `min` or more than
+/// `max` characters, e.g. single letters, or long strings of digits.
+#[derive(Debug, Clone, Copy)]
+pub struct LengthFilter {
+    min: usize,
+    max: usize,
+}
+
+impl LengthFilter {
+    /// Creates a new filter that keeps the tokens of `min` to `max`
+    /// characters.
+    pub fn new(min: usize, max: usize) -> Self {
+        Self { min, max }
+    }
+}
+
+impl TokenFilter for LengthFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .filter(|token| (self.min..=self.max).contains(&token.term().chars().count()))
+            .collect()
+    }
+}
+
+/// A filter that follows each token with its synonyms, so that e.g. a
+/// search for `car` finds the documents about automobiles.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::filter::{SynonymFilter, TokenFilter};
+/// use tokenize::Token;
+///
+/// let filter = SynonymFilter::new([vec!["car", "automobile"]]);
+/// let tokens = filter.filter(vec![Token::new("car", 0..3, 0)]);
+/// assert_eq!(
+///     tokens,
+///     [Token::new("car", 0..3, 0), Token::new("automobile", 0..3, 0)]
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SynonymFilter {
+    synonyms: HashMap<Term, Vec<Term>>,
+}
+
+impl SynonymFilter {
+    /// Creates a new filter from groups of synonyms. Each term of a group
+    /// is followed by the other terms of the group.
+    pub fn new<G, T>(groups: impl IntoIterator<Item = G>) -> Self
+    where
+        G: IntoIterator<Item = T>,
+        T: Into<Term>,
+    {
+        let mut synonyms: HashMap<Term, Vec<Term>> = HashMap::new();
+        for group in groups {
+            let group = group.into_iter().map(Into::into).collect::<Vec<_>>();
+            for term in &group {
+                let others = group.iter().filter(|other| *other != term).cloned();
+                synonyms.entry(term.clone()).or_default().extend(others);
+            }
+        }
+        Self { synonyms }
+    }
+}
+
+impl TokenFilter for SynonymFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        let mut filtered = Vec::with_capacity(tokens.len());
+        for token in tokens {
+            let synonyms = self.synonyms.get(token.term()).cloned().unwrap_or_default();
+            let synonyms = synonyms
+                .into_iter()
+                .map(|term| token.clone().with_term(term))
+                .collect::<Vec<_>>();
+            filtered.push(token);
+            filtered.extend(synonyms);
+        }
+        filtered
+    }
+}
+
+/// A filter that replaces each token with its character n-grams of `min`
+/// to `max` characters, so that parts of words are matched, e.g. `search`
+/// in `searchine`. Tokens with fewer than `min` characters are kept.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::filter::{NGramFilter, TokenFilter};
+/// use tokenize::Token;
+///
+/// let filter = NGramFilter::new(2, 3);
+/// let tokens = filter.filter(vec![Token::new("abcd", 0..4, 0), Token::new("a", 5..6, 1)]);
+/// let terms = tokens.iter().map(Token::term).collect::<Vec<_>>();
+/// assert_eq!(terms, ["ab", "abc", "bc", "bcd", "cd", "a"]);
+/// assert_eq!(tokens[4], Token::new("cd", 0..4, 0));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NGramFilter {
+    min: usize,
+    max: usize,
+}
+
+impl NGramFilter {
+    /// Creates a new filter into n-grams of `min` to `max` characters.
+    /// The lengths are at least 1.
+    pub fn new(min: usize, max: usize) -> Self {
+        let min = min.max(1);
+        Self {
+            min,
+            max: max.max(min),
+        }
+    }
+}
+
+impl TokenFilter for NGramFilter {
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        let mut filtered = Vec::new();
+        for token in tokens {
+            let chars = token.term().chars().collect::<Vec<_>>();
+            if chars.len() < self.min {
+                filtered.push(token);
+                continue;
+            }
+            for start in 0..chars.len() {
+                let lens = self.min..=self.max.min(chars.len() - start);
+                filtered.extend(lens.map(|len| {
+                    let term = chars[start..start + len].iter().collect::<Term>();
+                    token.clone().with_term(term)
+                }));
+            }
+        }
+        filtered
+    }
+}
+
+/// The description of a token filter in a configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FilterOptions {
+    /// Lowercases the tokens.
+    Lowercase,
+    /// Drops the stop words.
+    StopWords(StopWordOptions),
+    /// Stems the tokens with a stemmer, e.g. the Snowball stemmer of the
+    /// language of the tokenizer.
+    Stem(StemOptions),
+    /// Folds the tokens to ASCII.
+    Fold,
+    /// Drops the tokens with fewer than `min` or more than `max`
+    /// characters.
+    Length { min: usize, max: usize },
+    /// Follows the tokens with their synonyms, from groups of synonyms.
+    Synonyms { groups: Vec<Vec<String>> },
+    /// Replaces the tokens with their n-grams of `min` to `max` characters.
+    NGrams { min: usize, max: usize },
+}
+
+impl FilterOptions {
+    /// Creates the token filter that the options describe. It fails if a
+    /// file of the filter, e.g. the dictionary of a lemmatizer, cannot be
+    /// loaded.
+    pub fn filter(&self) -> io::Result<Box<dyn TokenFilter>> {
+        let filter: Box<dyn TokenFilter> = match self {
+            Self::Lowercase => Box::new(LowercaseFilter),
+            Self::StopWords(options) => Box::new(StopWordFilter::from_options(options)),
+            Self::Stem(options) => Box::new(StemFilter::from_options(options)?),
+            Self::Fold => Box::new(FoldFilter),
+            Self::Length { min, max } => Box::new(LengthFilter::new(*min, *max)),
+            Self::Synonyms { groups } => Box::new(SynonymFilter::new(groups.clone())),
+            Self::NGrams { min, max } => Box::new(NGramFilter::new(*min, *max)),
+        };
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(terms: &[&str]) -> Vec<Token> {
+        let tokens = terms.iter().enumerate();
+        tokens
+            .map(|(i, term)| Token::new(*term, i..i + 1, i))
+            .collect()
+    }
+
+    fn terms(tokens: Vec<Token>) -> Vec<Term> {
+        tokens.into_iter().map(Token::into_term).collect()
+    }
+
+    #[test]
+    fn test_filters() {
+        let filtered = LengthFilter::new(2, 4).filter(tokens(&["a", "ab", "abcde"]));
+        assert_eq!(filtered, [Token::new("ab", 1..2, 1)]);
+
+        let mut stem = StemFilter::new(Language::English);
+        assert_eq!(
+            stem.filter(tokens(&["running"])),
+            [Token::new("run", 0..1, 0)]
+        );
+        stem.set_language(Language::German);
+        assert_eq!(terms(stem.filter(tokens(&["häuser"]))), ["haus"]);
+
+        let synonyms = SynonymFilter::new([["tv", "television", "telly"]]);
+        let filtered = synonyms.filter(tokens(&["the", "telly"]));
+        assert_eq!(filtered[3], Token::new("television", 1..2, 1));
+        assert_eq!(terms(filtered), ["the", "telly", "tv", "television"]);
+    }
+
+    #[test]
+    fn test_filter_options() {
+        let options: Vec<FilterOptions> = serde_json::from_str(
+            r#"[
+                {"type": "lowercase"},
+                {"type": "stop_words", "languages": ["english"]},
+                {"type": "n_grams", "min": 3, "max": 3}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(options[0], FilterOptions::Lowercase);
+        assert_eq!(options[2], FilterOptions::NGrams { min: 3, max: 3 });
+        let filtered = options
+            .iter()
+            .fold(tokens(&["The", "Index"]), |tokens, options| {
+                options.filter().unwrap().filter(tokens)
+            });
+        assert_eq!(terms(filtered), ["ind", "nde", "dex"]);
+    }
+
+    #[test]
+    fn test_stop_word_languages() {
+        let options = StopWordOptions::default();
+        let mut filter = StopWordFilter::from_options(&options);
+        filter.set_language(Language::Spanish);
+        let filtered = filter.filter(tokens(&["los", "the", "documentos"]));
+        assert_eq!(terms(filtered), ["documentos"]);
+
+        let options = StopWordOptions {
+            languages: Vec::new(),
+            words: vec!["searchine".to_string()],
+        };
+        let mut filter = StopWordFilter::from_options(&options);
+        filter.set_language(Language::Spanish);
+        let filtered = filter.filter(tokens(&["los", "searchine"]));
+        assert_eq!(terms(filtered), ["los"]);
+    }
+
+    #[test]
+    fn test_stem_options() {
+        let options: Vec<FilterOptions> = serde_json::from_str(
+            r#"[
+                {"type": "stem"},
+                {"type": "stem", "stemmer": "light"},
+                {"type": "stem", "stemmer": "off"},
+                {"type": "stem", "stemmer": "lemmatizer"}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(options[0], FilterOptions::Stem(StemOptions::default()));
+        let stem = |options: &FilterOptions, term| {
+            let mut filter = options.filter().unwrap();
+            filter.set_language(Language::German);
+            terms(filter.filter(tokens(&[term])))
+        };
+        assert_eq!(stem(&options[0], "häuser"), ["haus"]);
+        assert_eq!(stem(&options[1], "queries"), ["query"]);
+        assert_eq!(stem(&options[2], "running"), ["running"]);
+        let err = options[3].filter().err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
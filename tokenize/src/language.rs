@@ -0,0 +1,132 @@
+use std::fmt;
+
+use rust_stemmers::Algorithm;
+use serde::{Deserialize, Serialize};
+
+/// A natural language that text is tokenized in. The languages are those
+/// that the Snowball project has stemmers and stop-word lists for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Danish,
+    Dutch,
+    #[default]
+    English,
+    Finnish,
+    French,
+    German,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Russian,
+    Spanish,
+    Swedish,
+}
+
+impl Language {
+    /// All the languages.
+    pub const ALL: [Language; 13] = [
+        Self::Danish,
+        Self::Dutch,
+        Self::English,
+        Self::Finnish,
+        Self::French,
+        Self::German,
+        Self::Hungarian,
+        Self::Italian,
+        Self::Norwegian,
+        Self::Portuguese,
+        Self::Russian,
+        Self::Spanish,
+        Self::Swedish,
+    ];
+
+    /// Returns the ISO 639-1 code of the language, e.g. `en`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Danish => "da",
+            Self::Dutch => "nl",
+            Self::English => "en",
+            Self::Finnish => "fi",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Hungarian => "hu",
+            Self::Italian => "it",
+            Self::Norwegian => "no",
+            Self::Portuguese => "pt",
+            Self::Russian => "ru",
+            Self::Spanish => "es",
+            Self::Swedish => "sv",
+        }
+    }
+
+    /// Returns the language with the specified ISO 639-1 code.
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    /// Returns the algorithm of the Snowball stemmer of the language.
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            Self::Danish => Algorithm::Danish,
+            Self::Dutch => Algorithm::Dutch,
+            Self::English => Algorithm::English,
+            Self::Finnish => Algorithm::Finnish,
+            Self::French => Algorithm::French,
+            Self::German => Algorithm::German,
+            Self::Hungarian => Algorithm::Hungarian,
+            Self::Italian => Algorithm::Italian,
+            Self::Norwegian => Algorithm::Norwegian,
+            Self::Portuguese => Algorithm::Portuguese,
+            Self::Russian => Algorithm::Russian,
+            Self::Spanish => Algorithm::Spanish,
+            Self::Swedish => Algorithm::Swedish,
+        }
+    }
+}
+
+/// Options that control the language that documents are stemmed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LanguageOptions {
+    /// The language that documents are stemmed in, unless their language
+    /// is detected.
+    pub default: Language,
+    /// Whether documents are stemmed in the language that is detected for
+    /// each of them. Documents whose language is not detected, or has no
+    /// stemmer, are stemmed in the default language.
+    pub detect: bool,
+}
+
+impl LanguageOptions {
+    /// Returns the language that a document is stemmed in, given the
+    /// language that was detected for it, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokenize::language::{Language, LanguageOptions};
+    ///
+    /// let options = LanguageOptions { default: Language::English, detect: true };
+    /// assert_eq!(options.stem_language(Some(Language::German)), Language::German);
+    /// assert_eq!(options.stem_language(None), Language::English);
+    ///
+    /// let options = LanguageOptions { default: Language::English, detect: false };
+    /// assert_eq!(options.stem_language(Some(Language::German)), Language::English);
+    /// ```
+    pub fn stem_language(&self, detected: Option<Language>) -> Language {
+        match detected {
+            Some(language) if self.detect => language,
+            _ => self.default,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
@@ -1,7 +1,74 @@
-pub use tokenizer::Tokenizer;
+pub use encode::{Encoder, TokenId};
+pub use language::{Language, LanguageOptions};
+pub use stop::{StopWordOptions, StopWords};
+pub use token::Token;
+pub use tokenizer::{Tokenizer, TokenizerOptions};
 
-mod encode;
+pub mod encode;
+pub mod filter;
+pub mod language;
+pub mod normalize;
 mod pre;
+pub mod stem;
+pub mod stop;
+mod token;
 pub mod tokenizer;
 
-pub type Token = String;
+/// A term, i.e. the text of a token, as it is indexed and searched.
+pub type Term = String;
+
+/// Returns the term that represents a term of a named text field,
+/// e.g. `comments:todo`, so that text fields are indexed separately
+/// from the page content. Terms start with an alphanumeric character, and
+/// the only terms of the page content with a colon are URLs, in which it
+/// is followed by `//`, so the two cannot collide.
+pub fn field_token(field: &str, term: &str) -> Term {
+    format!("{field}:{term}")
+}
+
+/// Splits a term of a named text field, as returned by [`field_token`],
+/// into the field and the term, e.g. `comments:todo` into `comments` and
+/// `todo`. URLs, in which the colon is followed by `//`, are not split.
+pub fn split_field_token(term: &str) -> Option<(&str, &str)> {
+    let (field, term) = term.split_once(':')?;
+    (!term.starts_with("//")).then_some((field, term))
+}
+
+/// Returns the terms of a vocabulary that contain a term of a single CJK
+/// character, e.g. `子猫` and `猫が` for `猫`. Runs of CJK characters are
+/// indexed by their bigrams, so a character is matched within them by the
+/// bigrams that it is part of. Other terms have none.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::cjk_bigrams;
+///
+/// let vocabulary = ["子猫", "猫が", "東京", "猫", "cat"];
+/// assert_eq!(cjk_bigrams("猫", vocabulary), ["子猫", "猫が"]);
+/// assert!(cjk_bigrams("東京", vocabulary).is_empty());
+/// ```
+pub fn cjk_bigrams<'a>(term: &str, vocabulary: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+    let mut chars = term.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return Vec::new();
+    };
+    if !pre::is_cjk(c) {
+        return Vec::new();
+    }
+    vocabulary
+        .into_iter()
+        .filter(|bigram| {
+            bigram.chars().count() == 2 && bigram.contains(c) && bigram.chars().all(pre::is_cjk)
+        })
+        .collect()
+}
+
+/// Returns the term that represents an exact term of a text, i.e. a
+/// case-preserved term that was not passed through the filters of the
+/// tokenizer, e.g. `=Rust`, so that exact terms are indexed next to the
+/// filtered ones. Terms start with an alphanumeric character, so the two
+/// cannot collide.
+pub fn exact_token(term: &str) -> Term {
+    format!("={term}")
+}
@@ -0,0 +1,180 @@
+use std::ops::Range;
+
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::pre::is_cjk;
+
+/// Normalizes text to its Unicode compatibility composition (NFKC), so
+/// that text that is typed differently but reads the same is the same,
+/// e.g. the ligature `ﬁ` and `fi`, or `e` followed by a combining accent
+/// and `é`.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::normalize::normalize;
+///
+/// assert_eq!(normalize("\u{fb01}le"), "file");
+/// assert_eq!(normalize("cafe\u{301}"), "café");
+/// ```
+pub fn normalize(text: &str) -> String {
+    text.nfkc().collect()
+}
+
+/// A text normalized to NFKC, that maps the normalized text back to the
+/// original text, so that tokens of the normalized text point to the
+/// original text. Only the spans that normalization changes are recorded,
+/// and the text between them maps to the original text byte for byte.
+pub(crate) struct NormalizedText {
+    text: String,
+    changes: Vec<Change>,
+}
+
+/// A span of the normalized text that differs from the span of the
+/// original text that it comes from.
+struct Change {
+    text: Range<usize>,
+    origin: Range<usize>,
+}
+
+impl NormalizedText {
+    /// Normalizes the text. Each character is normalized together with
+    /// the combining marks that follow it, since they may compose into a
+    /// single character.
+    pub(crate) fn new(original: &str) -> Self {
+        let mut text = String::with_capacity(original.len());
+        let mut changes = Vec::new();
+        let mut chars = original.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            let mut end = start + c.len_utf8();
+            while let Some(&(idx, mark)) = chars.peek() {
+                if !is_combining_mark(mark) {
+                    break;
+                }
+                end = idx + mark.len_utf8();
+                chars.next();
+            }
+            let len = text.len();
+            text.extend(original[start..end].nfkc());
+            if text[len..] != original[start..end] {
+                changes.push(Change {
+                    text: len..text.len(),
+                    origin: start..end,
+                });
+            }
+        }
+        Self { text, changes }
+    }
+
+    /// Returns the normalized text.
+    pub(crate) fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the range of the original text that a non-empty range of
+    /// the normalized text comes from.
+    pub(crate) fn origin(&self, range: Range<usize>) -> Range<usize> {
+        self.byte_origin(range.start).start..self.byte_origin(range.end - 1).end
+    }
+
+    /// Returns the range of the original text that a byte of the
+    /// normalized text comes from. The changes are sorted, so the one
+    /// that contains the byte, or else the last one before it, is found
+    /// with a binary search.
+    fn byte_origin(&self, offset: usize) -> Range<usize> {
+        let i = self
+            .changes
+            .partition_point(|change| change.text.end <= offset);
+        match self.changes.get(i) {
+            Some(change) if change.text.start <= offset => change.origin.clone(),
+            _ => {
+                let (text_end, origin_end) = match i.checked_sub(1) {
+                    Some(prev) => (self.changes[prev].text.end, self.changes[prev].origin.end),
+                    None => (0, 0),
+                };
+                let origin = origin_end + offset - text_end;
+                origin..origin + 1
+            }
+        }
+    }
+}
+
+/// Folds a lowercase term to ASCII letters where it can, by removing the
+/// accents of its letters, and by spelling out the letters that have no
+/// accents to remove, e.g. `ß` as `ss`. Letters of other scripts are kept,
+/// and CJK characters are kept whole, e.g. the voiced kana `が`.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::normalize::fold;
+///
+/// assert_eq!(fold("café"), "cafe");
+/// assert_eq!(fold("straße"), "strasse");
+/// assert_eq!(fold("ελλάδα"), "ελλαδα");
+/// ```
+pub fn fold(term: &str) -> String {
+    let mut folded = String::with_capacity(term.len());
+    for c in term.chars().flat_map(decompose) {
+        match c {
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            'œ' => folded.push_str("oe"),
+            'þ' => folded.push_str("th"),
+            'ø' => folded.push('o'),
+            'đ' | 'ð' => folded.push('d'),
+            'ł' => folded.push('l'),
+            'ı' => folded.push('i'),
+            c => folded.push(c),
+        }
+    }
+    folded.nfc().collect()
+}
+
+/// Decomposes a character into its letter and its accents, and drops the
+/// accents. CJK characters are not decomposed.
+fn decompose(c: char) -> Vec<char> {
+    if is_cjk(c) {
+        return vec![c];
+    }
+    c.nfd().filter(|&c| !is_combining_mark(c)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalized_text() {
+        let text = NormalizedText::new("\u{fb01}ne cafe\u{301}");
+        assert_eq!(text.as_str(), "fine café");
+        assert_eq!(text.origin(0..1), 0..3);
+        assert_eq!(text.origin(0..4), 0..5);
+        assert_eq!(text.origin(5..10), 6..12);
+        assert_eq!(text.changes.len(), 2);
+
+        let text = NormalizedText::new("x\u{2460} \u{fb01}");
+        assert_eq!(text.as_str(), "x1 fi");
+        assert_eq!(text.origin(0..1), 0..1);
+        assert_eq!(text.origin(1..2), 1..4);
+        assert_eq!(text.origin(2..3), 4..5);
+        assert_eq!(text.origin(3..5), 5..8);
+    }
+
+    #[test]
+    fn test_fold() {
+        let cases = [
+            ("naïve", "naive"),
+            ("smørrebrød", "smorrebrod"),
+            ("łódź", "lodz"),
+            ("encyclopædia", "encyclopaedia"),
+            ("résumé", "resume"),
+            ("東京", "東京"),
+            ("かがみ", "かがみ"),
+        ];
+        for (term, folded) in cases {
+            assert_eq!(fold(term), folded);
+        }
+    }
+}
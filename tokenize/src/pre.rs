@@ -1,7 +1,14 @@
+use std::ops::Range;
+
+use crate::normalize::NormalizedText;
+use crate::Token;
+
 /// A pre-tokenizer.
 ///
-/// This struct is used specifically to separate text into lowercase
-/// alphanumeric terms.
+/// This struct is used specifically to separate text into alphanumeric
+/// terms, which are then filtered by a tokenizer, e.g. lowercased. Each
+/// term is a token with the byte range of the original text that it comes
+/// from, and its position among the terms of the text.
 pub(crate) struct PreTokenizer;
 
 impl PreTokenizer {
@@ -10,7 +17,17 @@ impl PreTokenizer {
         Self
     }
 
-    /// Separates the input text into a vector of lowercase alphanumeric terms.
+    /// Separates the input text into a vector of alphanumeric terms.
+    /// The text is normalized to NFKC first, so that e.g. ligatures are
+    /// separated into their letters. Runs of CJK characters, which are not
+    /// separated by spaces, are separated into overlapping bigrams.
+    ///
+    /// Words that join alphanumeric parts with punctuation are kept whole
+    /// and are followed by their parts, so that they can be searched both
+    /// ways: technical terms such as `C++` and `C#`, dotted names and
+    /// numbers such as `node.js` and `3.14`, hyphenated compounds such as
+    /// `state-of-the-art`, email addresses and URLs. Possessives are
+    /// separated from their words, e.g. `Rust's` results in `Rust`.
     ///
     /// # Arguments
     ///
@@ -18,26 +35,369 @@ impl PreTokenizer {
     ///
     /// # Returns
     ///
-    /// A `Vec<String>` containing the lowercase alphanumeric terms extracted
-    /// from the input text.
-    pub(crate) fn separate_text(&self, text: impl AsRef<str>) -> Vec<String> {
-        let text = text.as_ref();
-        text.split(|c: char| !c.is_alphanumeric())
-            .filter(|s| !s.is_empty())
-            .map(|s| s.to_lowercase())
-            .collect()
+    /// A `Vec<Token>` containing the alphanumeric terms extracted from the
+    /// input text, numbered by their position. A whole word has the
+    /// position of its first part.
+    pub(crate) fn separate_text(&self, text: impl AsRef<str>) -> Vec<Token> {
+        let text = NormalizedText::new(text.as_ref());
+        let mut tokens = Vec::new();
+        let mut position = 0;
+        for word in text.as_str().split(char::is_whitespace) {
+            let word = trim_word(word);
+            let parts = word
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|s| !s.is_empty())
+                .flat_map(segment)
+                .collect::<Vec<_>>();
+            if parts != [word] && is_compound(word) {
+                tokens.push(text.token(word, position));
+            }
+            for part in parts {
+                tokens.push(text.token(part, position));
+                position += 1;
+            }
+        }
+        tokens
+    }
+
+    /// Separates source code into a vector of identifier terms.
+    ///
+    /// Identifiers that consist of multiple words, in camelCase,
+    /// PascalCase, snake_case or kebab-case, are kept whole and are
+    /// followed by their words. The code is normalized to NFKC first, and
+    /// identifiers with CJK characters are separated like text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - An input text that can be referenced as a string slice.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<Token>` containing the identifiers and their words extracted
+    /// from the input text. A whole identifier has the position of its
+    /// first word.
+    pub(crate) fn separate_code(&self, text: impl AsRef<str>) -> Vec<Token> {
+        let text = NormalizedText::new(text.as_ref());
+        let is_identifier_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+        let mut tokens = Vec::new();
+        let mut position = 0;
+        for identifier in text.as_str().split(|c: char| !is_identifier_char(c)) {
+            let identifier = identifier.trim_matches(|c| c == '_' || c == '-');
+            if identifier.is_empty() {
+                continue;
+            }
+            let words = match identifier.chars().any(is_cjk) {
+                true => segment(identifier),
+                false => split_identifier(identifier),
+            };
+            if words.len() > 1 && !identifier.chars().any(is_cjk) {
+                tokens.push(text.token(identifier, position));
+            }
+            for word in words {
+                tokens.push(text.token(word, position));
+                position += 1;
+            }
+        }
+        tokens
     }
 }
 
+impl NormalizedText {
+    /// Returns the token of a term, which is a slice of the normalized
+    /// text, with the range of the original text that it comes from.
+    fn token(&self, term: &str, position: usize) -> Token {
+        let offsets = self.origin(range_of(self.as_str(), term));
+        Token::new(term, offsets, position)
+    }
+}
+
+/// Returns the byte range of a slice of a text in the text.
+fn range_of(text: &str, slice: &str) -> Range<usize> {
+    let start = slice.as_ptr() as usize - text.as_ptr() as usize;
+    start..start + slice.len()
+}
+
+/// Trims the punctuation around a word of text, e.g. quotes, brackets and
+/// the punctuation of the sentence, which includes the apostrophe of plural
+/// possessives, e.g. `users'`, and the possessive `'s`. The signs that end
+/// technical terms, e.g. `C++` and `C#`, are kept.
+fn trim_word(word: &str) -> &str {
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let word = word.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '+' && c != '#');
+    ["'s", "\u{2019}s"]
+        .into_iter()
+        .find_map(|possessive| word.strip_suffix(possessive))
+        .unwrap_or(word)
+}
+
+/// Returns true if a word that joins alphanumeric parts with punctuation is
+/// kept whole, i.e. if it is a technical term such as `C++` or `C#`, parts
+/// joined by dots, hyphens or the commas of numbers, such as `node.js`,
+/// `3.14` or `1,000`, an email address, or a URL. Words with CJK characters
+/// are not kept whole.
+fn is_compound(word: &str) -> bool {
+    if word.is_empty() || word.chars().any(is_cjk) {
+        return false;
+    }
+    is_url(word) || is_email(word) || is_technical_term(word) || is_joined(word)
+}
+
+/// Returns true if the word is a URL, i.e. it starts with a scheme followed
+/// by `://`, e.g. `https://`, or with `www.`.
+fn is_url(word: &str) -> bool {
+    if word.starts_with("www.") {
+        return word.len() > 4;
+    }
+    let Some((scheme, rest)) = word.split_once("://") else {
+        return false;
+    };
+    let is_scheme_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-');
+    scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme.chars().all(is_scheme_char)
+        && !rest.is_empty()
+}
+
+/// Returns true if the word is an email address, e.g. `user@example.com`.
+fn is_email(word: &str) -> bool {
+    let Some((local, domain)) = word.split_once('@') else {
+        return false;
+    };
+    let is_local_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-');
+    let is_domain_char = |c: char| c.is_alphanumeric() || matches!(c, '.' | '-');
+    !local.is_empty()
+        && local.chars().all(is_local_char)
+        && domain.contains('.')
+        && domain.chars().all(is_domain_char)
+}
+
+/// Returns true if the word is a technical term that ends with plus signs
+/// or a sharp sign, e.g. `C++`, `g++` or `F#`.
+fn is_technical_term(word: &str) -> bool {
+    let name = match word.strip_suffix('#') {
+        Some(name) => name,
+        None => word.trim_end_matches('+'),
+    };
+    name.len() < word.len() && !name.is_empty() && name.chars().all(char::is_alphanumeric)
+}
+
+/// Returns true if the word consists of alphanumeric parts that are joined
+/// by single dots or hyphens, or by commas between digits, e.g. `node.js`,
+/// `v1.2.3`, `state-of-the-art` or `1,000`.
+fn is_joined(word: &str) -> bool {
+    let chars = word.chars().collect::<Vec<_>>();
+    chars.iter().enumerate().all(|(i, &c)| {
+        let prev = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1).copied();
+        match c {
+            '.' | '-' => {
+                prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric)
+            }
+            ',' => {
+                prev.is_some_and(|c| c.is_ascii_digit()) && next.is_some_and(|c| c.is_ascii_digit())
+            }
+            c => c.is_alphanumeric(),
+        }
+    })
+}
+
+/// Returns true if the character is of a CJK script, i.e. a Chinese
+/// character, Japanese kana, or Korean hangul. These scripts do not
+/// separate words with spaces.
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{1100}'..='\u{11FF}'     // Hangul Jamo
+        | '\u{3040}'..='\u{30FF}'   // Hiragana and Katakana
+        | '\u{3130}'..='\u{318F}'   // Hangul Compatibility Jamo
+        | '\u{31F0}'..='\u{31FF}'   // Katakana Phonetic Extensions
+        | '\u{3400}'..='\u{4DBF}'   // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}'   // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}'   // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}'   // CJK Compatibility Ideographs
+        | '\u{20000}'..='\u{2FA1F}' // CJK Unified Ideographs Extensions B to F
+    )
+}
+
+/// Separates a word into terms. The runs of CJK characters of the word
+/// are separated into overlapping bigrams, e.g. `東京都` into `東京` and
+/// `京都`, so that words can be matched within them without a dictionary,
+/// and a run of a single character is kept as it is. The other runs of
+/// the word are kept whole.
+fn segment(word: &str) -> Vec<&str> {
+    if !word.chars().any(is_cjk) {
+        return vec![word];
+    }
+    let mut terms = Vec::new();
+    let chars = word.char_indices().collect::<Vec<_>>();
+    let end = |&(idx, c): &(usize, char)| idx + c.len_utf8();
+    for run in chars.chunk_by(|(_, a), (_, b)| is_cjk(*a) == is_cjk(*b)) {
+        let (start, c) = run[0];
+        if !is_cjk(c) || run.len() == 1 {
+            terms.push(&word[start..end(&run[run.len() - 1])]);
+        } else {
+            let bigrams = run.windows(2);
+            terms.extend(bigrams.map(|bigram| &word[bigram[0].0..end(&bigram[1])]));
+        }
+    }
+    terms
+}
+
+/// Splits an identifier into its words, at underscores, hyphens and
+/// changes of case. A run of capitals is kept together as an acronym,
+/// e.g. `HTTPServer` is split into `HTTP` and `Server`.
+fn split_identifier(identifier: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in identifier.split(['_', '-']).filter(|p| !p.is_empty()) {
+        let chars = part.char_indices().collect::<Vec<_>>();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (idx, c) = chars[i];
+            let prev = chars[i - 1].1;
+            let next = chars.get(i + 1).map(|&(_, c)| c);
+            let is_boundary = c.is_uppercase()
+                && (!prev.is_uppercase() || next.is_some_and(char::is_lowercase));
+            if is_boundary {
+                words.push(&part[start..idx]);
+                start = idx;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn terms(tokens: Vec<Token>) -> Vec<String> {
+        tokens.into_iter().map(Token::into_term).collect()
+    }
+
     #[test]
     fn test_pre_tokenizer() {
         let splitter = PreTokenizer::new();
         let text = "I want it, i got it.";
         let words = splitter.separate_text(text);
-        assert_eq!(words, vec!["i", "want", "it", "i", "got", "it"]);
+        assert_eq!(words[1], Token::new("want", 2..6, 1));
+        assert_eq!(terms(words), vec!["I", "want", "it", "i", "got", "it"]);
+    }
+
+    #[test]
+    fn test_compound_pre_tokenizer() {
+        let splitter = PreTokenizer::new();
+        let text = "I code in C++ and C#, (with node.js) since version 3.14.";
+        let words = splitter.separate_text(text);
+        assert_eq!(words[3], Token::new("C++", 10..13, 3));
+        assert_eq!(words[4], Token::new("C", 10..11, 3));
+        assert_eq!(
+            terms(words),
+            vec![
+                "I", "code", "in", "C++", "C", "and", "C#", "C", "with", "node.js", "node", "js",
+                "since", "version", "3.14", "3", "14"
+            ]
+        );
+        let text = "Mail user@example.com, see <https://example.com/docs>.";
+        assert_eq!(
+            terms(splitter.separate_text(text)),
+            vec![
+                "Mail",
+                "user@example.com",
+                "user",
+                "example",
+                "com",
+                "see",
+                "https://example.com/docs",
+                "https",
+                "example",
+                "com",
+                "docs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hyphen_possessive_pre_tokenizer() {
+        let splitter = PreTokenizer::new();
+        let text = "Rust's state-of-the-art borrow checker, the users' 1,000 crates";
+        let words = splitter.separate_text(text);
+        assert_eq!(words[0], Token::new("Rust", 0..4, 0));
+        assert_eq!(words[1], Token::new("state-of-the-art", 7..23, 1));
+        assert_eq!(words[6].position(), 5);
+        assert_eq!(
+            terms(words),
+            vec![
+                "Rust",
+                "state-of-the-art",
+                "state",
+                "of",
+                "the",
+                "art",
+                "borrow",
+                "checker",
+                "the",
+                "users",
+                "1,000",
+                "1",
+                "000",
+                "crates"
+            ]
+        );
+        let words = splitter.separate_text("don't -- a/b");
+        assert_eq!(terms(words), vec!["don", "t", "a", "b"]);
+    }
+
+    #[test]
+    fn test_cjk_pre_tokenizer() {
+        let splitter = PreTokenizer::new();
+        let words = splitter.separate_text("東京タワー is in 東京. 私はRust言語が好き");
+        assert_eq!(words[1].offsets(), 3..9);
+        assert_eq!(
+            terms(words),
+            vec![
+                "東京", "京タ", "タワ", "ワー", "is", "in", "東京", "私は", "Rust", "言語", "語が",
+                "が好", "好き"
+            ]
+        );
+        assert_eq!(terms(splitter.separate_text("猫")), vec!["猫"]);
+        assert_eq!(
+            terms(splitter.separate_code("let 名前 = 1;")),
+            vec!["let", "名前", "1"]
+        );
+    }
+
+    #[test]
+    fn test_normalized_pre_tokenizer() {
+        let splitter = PreTokenizer::new();
+        let words = splitter.separate_text("The \u{fb01}le at the Cafe\u{301}");
+        assert_eq!(words[1], Token::new("file", 4..9, 1));
+        assert_eq!(words[4], Token::new("Café", 17..23, 4));
+        assert_eq!(terms(words), vec!["The", "file", "at", "the", "Café"]);
+    }
+
+    #[test]
+    fn test_code_pre_tokenizer() {
+        let splitter = PreTokenizer::new();
+        let text = "let cfg = parseConfig(HTTPServer::new(), parse_config_file);";
+        let words = splitter.separate_code(text);
+        assert_eq!(words[2], Token::new("parseConfig", 10..21, 2));
+        assert_eq!(words[4], Token::new("Config", 15..21, 3));
+        assert_eq!(
+            terms(words),
+            vec![
+                "let",
+                "cfg",
+                "parseConfig",
+                "parse",
+                "Config",
+                "HTTPServer",
+                "HTTP",
+                "Server",
+                "new",
+                "parse_config_file",
+                "parse",
+                "config",
+                "file",
+            ]
+        );
     }
 }
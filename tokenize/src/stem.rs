@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The stemmer that the stem filter reduces words with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StemmerKind {
+    /// The Snowball stemmer of the language of the text, e.g. `running`
+    /// to `run`.
+    #[default]
+    Snowball,
+    /// A light stemmer, that only removes the plural endings of English
+    /// words, e.g. `queries` to `query`.
+    Light,
+    /// A lemmatizer, that looks words up in a dictionary of their lemmas,
+    /// e.g. `better` to `good`.
+    Lemmatizer,
+    /// No stemming, e.g. for code, logs or names.
+    Off,
+}
+
+/// Options that control how the stem filter reduces words.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StemOptions {
+    /// The stemmer that words are reduced with.
+    pub stemmer: StemmerKind,
+    /// The dictionary file of the lemmatizer, which is required if the
+    /// stemmer is the lemmatizer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dictionary: Option<PathBuf>,
+}
+
+/// Stems an English word lightly, by removing its plural ending, with the
+/// rules of the S stemmer: `ies` becomes `y`, and otherwise `s` is removed,
+/// unless the word ends in a way that is not plural, e.g. `ss`.
+/// Words of up to three letters are kept, e.g. `has`.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::stem::light_stem;
+///
+/// assert_eq!(light_stem("queries"), "query");
+/// assert_eq!(light_stem("tables"), "table");
+/// assert_eq!(light_stem("documents"), "document");
+/// assert_eq!(light_stem("class"), "class");
+/// ```
+pub fn light_stem(word: &str) -> String {
+    if word.chars().count() <= 3 {
+        return word.to_string();
+    }
+    let ends_with_any = |suffixes: &[&str]| suffixes.iter().any(|s| word.ends_with(s));
+    if word.ends_with("ies") && !ends_with_any(&["eies", "aies"]) {
+        format!("{}y", &word[..word.len() - 3])
+    } else if word.ends_with('s') && !ends_with_any(&["us", "ss"]) {
+        word[..word.len() - 1].to_string()
+    } else {
+        word.to_string()
+    }
+}
+
+/// A dictionary-based lemmatizer, that reduces words to their lemmas, i.e.
+/// their dictionary forms, e.g. `better` to `good` and `mice` to `mouse`.
+/// Words that are not in the dictionary are kept.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::stem::Lemmatizer;
+///
+/// let lemmatizer = Lemmatizer::new()
+///     .with_lemma("better", "good")
+///     .with_lemma("Mice", "mouse");
+/// assert_eq!(lemmatizer.lemma("better"), "good");
+/// assert_eq!(lemmatizer.lemma("mice"), "mouse");
+/// assert_eq!(lemmatizer.lemma("index"), "index");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Lemmatizer {
+    lemmas: HashMap<String, String>,
+}
+
+impl Lemmatizer {
+    /// Creates a new lemmatizer with an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the dictionary of a lemmatizer from a file. Each line of the
+    /// file has a word and its lemma, separated by whitespace, e.g.
+    /// `better good`. Empty lines and lines that start with `#` are
+    /// skipped.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let mut lemmatizer = Self::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [word, lemma] => lemmatizer = lemmatizer.with_lemma(word, lemma),
+                _ => {
+                    let msg = format!(
+                        "{}:{}: expected a word and its lemma",
+                        path.display(),
+                        i + 1
+                    );
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+                }
+            }
+        }
+        Ok(lemmatizer)
+    }
+
+    /// Adds a word and its lemma to the dictionary. Both are lowercased.
+    pub fn with_lemma(mut self, word: &str, lemma: &str) -> Self {
+        self.lemmas
+            .insert(word.to_lowercase(), lemma.to_lowercase());
+        self
+    }
+
+    /// Returns the lemma of a lowercase word, or the word if it is not in
+    /// the dictionary.
+    pub fn lemma<'a>(&'a self, word: &'a str) -> &'a str {
+        self.lemmas.get(word).map_or(word, String::as_str)
+    }
+
+    /// Returns the number of words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.lemmas.len()
+    }
+
+    /// Returns true if the dictionary is empty.
+    pub fn is_empty(&self) -> bool {
+        self.lemmas.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_stem() {
+        let cases = [
+            ("libraries", "library"),
+            ("cookies", "cooky"),
+            ("goes", "goe"),
+            ("tables", "table"),
+            ("status", "status"),
+            ("was", "was"),
+            ("rust", "rust"),
+        ];
+        for (word, stem) in cases {
+            assert_eq!(light_stem(word), stem);
+        }
+    }
+
+    #[test]
+    fn test_lemmatizer_from_file() {
+        let path = std::env::temp_dir().join(format!("searchine-lemmas-{}", std::process::id()));
+        fs::write(&path, "# English\nbetter good\n\nran  run\n").unwrap();
+        let lemmatizer = Lemmatizer::from_file(&path).unwrap();
+        assert_eq!(lemmatizer.len(), 2);
+        assert_eq!(lemmatizer.lemma("ran"), "run");
+        fs::write(&path, "better\n").unwrap();
+        let err = Lemmatizer::from_file(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(path).unwrap();
+    }
+}
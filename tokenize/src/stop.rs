@@ -0,0 +1,195 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::language::Language;
+use crate::Term;
+
+/// Options that control which words are dropped from the tokens of text,
+/// because they are too common to tell documents apart, e.g. "the".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StopWordOptions {
+    /// The languages whose built-in stop-word lists are used.
+    pub languages: Vec<Language>,
+    /// Additional stop words, e.g. words that occur in every document
+    /// of the collection.
+    pub words: Vec<String>,
+}
+
+impl Default for StopWordOptions {
+    fn default() -> Self {
+        Self {
+            languages: vec![Language::English],
+            words: Vec::new(),
+        }
+    }
+}
+
+/// A set of stop words, which are dropped from the tokens of text.
+///
+/// The stop words are lowercase words, so the filter that drops them,
+/// [`StopWordFilter`], follows the lowercase filter, and comes before the
+/// stem filter.
+///
+/// [`StopWordFilter`]: crate::filter::StopWordFilter
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::language::Language;
+/// use tokenize::stop::StopWords;
+///
+/// let stop_words = StopWords::new()
+///     .with_language(Language::English)
+///     .with_words(["searchine"]);
+/// assert!(stop_words.contains("the"));
+/// assert!(stop_words.contains("searchine"));
+/// assert!(!stop_words.contains("index"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StopWords {
+    words: HashSet<String>,
+}
+
+impl StopWords {
+    /// Creates a new, empty set of stop words.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the set of stop words that the options specify.
+    pub fn from_options(options: &StopWordOptions) -> Self {
+        options
+            .languages
+            .iter()
+            .fold(Self::new(), |stop_words, &language| {
+                stop_words.with_language(language)
+            })
+            .with_words(&options.words)
+    }
+
+    /// Adds the built-in stop words of the language.
+    pub fn with_language(self, language: Language) -> Self {
+        self.with_words(builtin(language).split_whitespace())
+    }
+
+    /// Adds the specified stop words, which are lowercased.
+    pub fn with_words(mut self, words: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        let words = words.into_iter().map(|word| word.as_ref().to_lowercase());
+        self.words.extend(words);
+        self
+    }
+
+    /// Returns true if the lowercase word is a stop word.
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// Returns the number of stop words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns true if there are no stop words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Returns the built-in stop words of the language, separated by whitespace.
+fn builtin(language: Language) -> &'static str {
+    match language {
+        Language::Danish => include_str!("../stopwords/danish.txt"),
+        Language::Dutch => include_str!("../stopwords/dutch.txt"),
+        Language::English => include_str!("../stopwords/english.txt"),
+        Language::Finnish => include_str!("../stopwords/finnish.txt"),
+        Language::French => include_str!("../stopwords/french.txt"),
+        Language::German => include_str!("../stopwords/german.txt"),
+        Language::Hungarian => include_str!("../stopwords/hungarian.txt"),
+        Language::Italian => include_str!("../stopwords/italian.txt"),
+        Language::Norwegian => include_str!("../stopwords/norwegian.txt"),
+        Language::Portuguese => include_str!("../stopwords/portuguese.txt"),
+        Language::Russian => include_str!("../stopwords/russian.txt"),
+        Language::Spanish => include_str!("../stopwords/spanish.txt"),
+        Language::Swedish => include_str!("../stopwords/swedish.txt"),
+    }
+}
+
+/// Suggests stop words for a collection of documents, from the number of
+/// documents that each term occurs in. The terms that occur in at least
+/// `min_ratio` of the `n_docs` documents are returned with the ratio of
+/// the documents they occur in, from the most to the least common.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::stop::suggest;
+///
+/// let frequencies = [("the".to_string(), 98), ("index".to_string(), 12)];
+/// let suggested = suggest(frequencies, 100, 0.5);
+/// assert_eq!(suggested, [("the".to_string(), 0.98)]);
+/// ```
+pub fn suggest(
+    doc_frequencies: impl IntoIterator<Item = (Term, u32)>,
+    n_docs: u32,
+    min_ratio: f64,
+) -> Vec<(Term, f64)> {
+    if n_docs == 0 {
+        return Vec::new();
+    }
+    let mut suggested = doc_frequencies
+        .into_iter()
+        .map(|(term, n_docs_containing)| (term, n_docs_containing as f64 / n_docs as f64))
+        .filter(|(_, ratio)| *ratio >= min_ratio)
+        .collect::<Vec<_>>();
+    suggested.sort_by(|(term_a, ratio_a), (term_b, ratio_b)| {
+        ratio_b
+            .partial_cmp(ratio_a)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| term_a.cmp(term_b))
+    });
+    suggested
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stop_words() {
+        let options = StopWordOptions {
+            languages: vec![Language::German, Language::French],
+            words: vec!["Lorem".to_string()],
+        };
+        let stop_words = StopWords::from_options(&options);
+        assert!(stop_words.contains("und"));
+        assert!(stop_words.contains("été"));
+        assert!(stop_words.contains("lorem"));
+        assert!(!stop_words.contains("the"));
+        for language in Language::ALL {
+            assert!(!StopWords::new().with_language(language).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_suggest() {
+        let frequencies = [
+            ("a".to_string(), 10),
+            ("of".to_string(), 10),
+            ("the".to_string(), 9),
+            ("rust".to_string(), 2),
+        ];
+        let suggested = suggest(frequencies, 10, 0.9);
+        assert_eq!(
+            suggested,
+            [
+                ("a".to_string(), 1.0),
+                ("of".to_string(), 1.0),
+                ("the".to_string(), 0.9)
+            ]
+        );
+        assert!(suggest(Vec::new(), 0, 0.5).is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+use std::ops::Range;
+
+use crate::Term;
+
+/// A token of a text, i.e. a term with the byte range of the original
+/// text that it comes from, and its position among the terms of the text.
+///
+/// The filters of a tokenizer change the term of a token, but keep its
+/// offsets and position, so that e.g. a stemmed term still points to the
+/// word it was stemmed from. Tokens that a filter adds for a token, e.g.
+/// its synonyms, share the offsets and position of the token.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::Tokenizer;
+///
+/// let mut tokenizer = Tokenizer::default();
+/// let tokens = tokenizer.tokenize("Searching documents");
+/// assert_eq!(tokens[1].term(), "document");
+/// assert_eq!(tokens[1].offsets(), 10..19);
+/// assert_eq!(tokens[1].position(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    term: Term,
+    offsets: Range<usize>,
+    position: usize,
+}
+
+impl Token {
+    /// Creates a new token of a term at the offsets and position.
+    pub fn new(term: impl Into<Term>, offsets: Range<usize>, position: usize) -> Self {
+        Self {
+            term: term.into(),
+            offsets,
+            position,
+        }
+    }
+
+    /// Returns the term of the token.
+    pub fn term(&self) -> &str {
+        &self.term
+    }
+
+    /// Returns the term of the token, consuming the token.
+    pub fn into_term(self) -> Term {
+        self.term
+    }
+
+    /// Returns the byte range of the original text that the token comes
+    /// from.
+    pub fn offsets(&self) -> Range<usize> {
+        self.offsets.clone()
+    }
+
+    /// Returns the position of the token among the terms of the text.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the token with its term replaced, at the same offsets and
+    /// position.
+    pub fn with_term(self, term: impl Into<Term>) -> Self {
+        Self {
+            term: term.into(),
+            ..self
+        }
+    }
+}
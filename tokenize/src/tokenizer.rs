@@ -1,39 +1,177 @@
-use rust_stemmers::{Algorithm, Stemmer};
+use std::io;
 
+use serde::{Deserialize, Serialize};
+
+use crate::filter::{FilterOptions, LowercaseFilter, StemFilter, TokenFilter};
+use crate::language::Language;
 use crate::pre::PreTokenizer;
+use crate::stem::StemOptions;
+use crate::stop::StopWordOptions;
 use crate::Token;
 
-/// A tokenizer that processes input text into tokens, and stems them.
+/// A tokenizer that separates input text into tokens, and passes them
+/// through a chain of token filters, e.g. to lowercase them, drop the
+/// stop words and stem them.
+///
+/// # Examples
+///
+/// ```
+/// use tokenize::filter::{LengthFilter, LowercaseFilter};
+/// use tokenize::Tokenizer;
+///
+/// let mut tokenizer = Tokenizer::new()
+///     .with_filter(LowercaseFilter)
+///     .with_filter(LengthFilter::new(2, 20));
+/// let tokens = tokenizer.tokenize("A Tale of Two Cities");
+/// let terms = tokens.iter().map(|token| token.term()).collect::<Vec<_>>();
+/// assert_eq!(terms, ["tale", "of", "two", "cities"]);
+/// ```
 pub struct Tokenizer {
     pre_tokenizer: PreTokenizer,
-    stemmer: Stemmer,
+    filters: Vec<Box<dyn TokenFilter>>,
+    language: Language,
 }
 
 impl Default for Tokenizer {
-    /// Creates a new builder with a default pre-tokenizer and stemmer,
-    /// and no encoder.
+    /// Creates a new tokenizer that lowercases the tokens, and stems them
+    /// in English.
     fn default() -> Self {
+        Self::new()
+            .with_filter(LowercaseFilter)
+            .with_filter(StemFilter::new(Language::English))
+    }
+}
+
+impl Tokenizer {
+    /// Creates a new tokenizer without filters, that only separates the
+    /// text into tokens.
+    pub fn new() -> Self {
         Self {
             pre_tokenizer: PreTokenizer::new(),
-            stemmer: Stemmer::create(Algorithm::English),
+            filters: Vec::new(),
+            language: Language::default(),
         }
     }
-}
 
-impl Tokenizer {
+    /// Adds a filter to the end of the chain of filters. The filter is
+    /// set to the language of the tokenizer.
+    pub fn with_filter(mut self, mut filter: impl TokenFilter + 'static) -> Self {
+        filter.set_language(self.language);
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Adds a boxed filter to the end of the chain of filters.
+    pub fn with_boxed_filter(mut self, mut filter: Box<dyn TokenFilter>) -> Self {
+        filter.set_language(self.language);
+        self.filters.push(filter);
+        self
+    }
+
+    /// Sets the language of the text that is tokenized, e.g. the language
+    /// whose stemmer the tokens are stemmed with.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.set_language(language);
+        self
+    }
+
+    /// Changes the language of the text that is tokenized, e.g. to the
+    /// language of each document that is tokenized.
+    pub fn set_language(&mut self, language: Language) {
+        self.language = language;
+        for filter in &mut self.filters {
+            filter.set_language(language);
+        }
+    }
+
+    /// Returns the language of the text that is tokenized.
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Returns true if a filter of the tokenizer drops the word as a stop
+    /// word, whatever its case, e.g. `The`.
+    pub fn is_stop_word(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        self.filters.iter().any(|filter| filter.is_stop_word(&word))
+    }
+
+    /// Passes the tokens through the chain of filters.
+    fn filter(&self, tokens: Vec<Token>) -> Vec<Token> {
+        self.filters
+            .iter()
+            .fold(tokens, |tokens, filter| filter.filter(tokens))
+    }
+
+    /// Separates the input text into tokens, without passing them through
+    /// the filters, so that their terms are the exact, case-preserved words
+    /// of the text, only normalized to NFKC.
+    pub fn pre_tokenize(&self, text: impl AsRef<str>) -> Vec<Token> {
+        self.pre_tokenizer.separate_text(text)
+    }
+
+    /// Separates source code into tokens, without passing them through the
+    /// filters, like [`Tokenizer::pre_tokenize`].
+    pub fn pre_tokenize_code(&self, text: impl AsRef<str>) -> Vec<Token> {
+        self.pre_tokenizer.separate_code(text)
+    }
+
     /// Tokenizes the input text.
     ///
-    /// The input text is separated into lowercase alphanumeric terms,
-    /// stemmed, and returned as a vector of strings.
+    /// The input text is normalized and separated into alphanumeric
+    /// terms, which are passed through the filters and returned as a
+    /// vector of tokens, with their offsets in the input text.
     pub fn tokenize(&mut self, text: impl AsRef<str>) -> Vec<Token> {
         let tokens = self.pre_tokenizer.separate_text(text);
-        tokens
+        self.filter(tokens)
+    }
+
+    /// Tokenizes source code.
+    ///
+    /// The input code is separated into identifiers, and the identifiers
+    /// that consist of multiple words are followed by their words, e.g.
+    /// `parseConfig` results in `parseConfig`, `parse` and `Config`. The
+    /// terms are passed through the filters, e.g. to be lowercased and
+    /// stemmed to `parseconfig`, `pars` and `config`.
+    pub fn tokenize_code(&mut self, text: impl AsRef<str>) -> Vec<Token> {
+        let tokens = self.pre_tokenizer.separate_code(text);
+        self.filter(tokens)
+    }
+}
+
+/// Options that describe the chain of filters of a tokenizer, so that the
+/// documents and the queries are tokenized with the same chain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TokenizerOptions {
+    /// The filters, in the order that the tokens are passed through them.
+    pub filters: Vec<FilterOptions>,
+}
+
+impl Default for TokenizerOptions {
+    /// Lowercases the tokens, drops the English stop words, and stems the
+    /// tokens.
+    fn default() -> Self {
+        Self {
+            filters: vec![
+                FilterOptions::Lowercase,
+                FilterOptions::StopWords(StopWordOptions::default()),
+                FilterOptions::Stem(StemOptions::default()),
+            ],
+        }
+    }
+}
+
+impl TokenizerOptions {
+    /// Returns a tokenizer with the chain of filters of the options. It
+    /// fails if a filter cannot be created, e.g. if the dictionary of a
+    /// lemmatizer cannot be loaded.
+    pub fn tokenizer(&self) -> io::Result<Tokenizer> {
+        self.filters
             .iter()
-            .map(|token| {
-                let stem = self.stemmer.stem(token).to_string();
-                stem
+            .try_fold(Tokenizer::new(), |tokenizer, filter| {
+                Ok(tokenizer.with_boxed_filter(filter.filter()?))
             })
-            .collect::<Vec<_>>()
     }
 }
 
@@ -42,6 +180,11 @@ mod tests {
     use rust_stemmers::{Algorithm, Stemmer};
 
     use super::*;
+    use crate::Term;
+
+    fn terms(tokens: Vec<Token>) -> Vec<Term> {
+        tokens.into_iter().map(Token::into_term).collect()
+    }
 
     #[test]
     fn test_stemmer() {
@@ -55,8 +198,9 @@ mod tests {
         let text = "I want it. I got it.".to_string();
         let mut tokenizer = Tokenizer::default();
         let tokens = tokenizer.tokenize(text);
+        assert_eq!(tokens[4], Token::new("got", 13..16, 4));
         assert_eq!(
-            tokens,
+            terms(tokens),
             vec![
                 "i".to_string(),
                 "want".to_string(),
@@ -67,4 +211,54 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_code_tokenization() {
+        let mut tokenizer = Tokenizer::default();
+        let tokens = tokenizer.tokenize_code("parseConfig()");
+        assert_eq!(terms(tokens), vec!["parseconfig", "pars", "config"]);
+    }
+
+    #[test]
+    fn test_stop_words() {
+        let mut tokenizer = TokenizerOptions::default().tokenizer().unwrap();
+        let tokens = tokenizer.tokenize("The index of the documents");
+        assert_eq!(tokens[1], Token::new("document", 17..26, 4));
+        assert_eq!(terms(tokens), vec!["index", "document"]);
+        let tokens = tokenizer.tokenize_code("for item in items");
+        assert_eq!(terms(tokens), vec!["item", "item"]);
+        assert!(tokenizer.is_stop_word("The"));
+        assert!(!tokenizer.is_stop_word("Index"));
+    }
+
+    #[test]
+    fn test_pre_tokenization() {
+        let tokenizer = TokenizerOptions::default().tokenizer().unwrap();
+        let tokens = tokenizer.pre_tokenize("The Rusting parts");
+        assert_eq!(terms(tokens), vec!["The", "Rusting", "parts"]);
+        let tokens = tokenizer.pre_tokenize_code("parseConfig()");
+        assert_eq!(terms(tokens), vec!["parseConfig", "parse", "Config"]);
+    }
+
+    #[test]
+    fn test_language() {
+        let mut tokenizer = Tokenizer::default().with_language(Language::German);
+        assert_eq!(terms(tokenizer.tokenize("Häuser")), vec!["haus"]);
+        tokenizer.set_language(Language::Spanish);
+        assert_eq!(tokenizer.language(), Language::Spanish);
+        assert_eq!(terms(tokenizer.tokenize("documentos")), vec!["document"]);
+    }
+
+    #[test]
+    fn test_folding() {
+        let mut tokenizer = Tokenizer::default().with_filter(crate::filter::FoldFilter);
+        assert_eq!(terms(tokenizer.tokenize("Café")), vec!["cafe"]);
+        assert_eq!(
+            tokenizer.tokenize("\u{fb01}les"),
+            [Token::new("file", 0..6, 0)]
+        );
+        let mut tokenizer = tokenizer.with_language(Language::German);
+        assert_eq!(terms(tokenizer.tokenize("Häuser")), vec!["haus"]);
+        assert_eq!(terms(tokenizer.tokenize("Straße")), vec!["strass"]);
+    }
 }